@@ -0,0 +1,77 @@
+#![feature(test)]
+
+extern crate test;
+
+const FENS: [&str; 4] = [
+    "x5o/7/7/7/7/7/o5x x 0 1",
+    "x5o/7/2-1-2/7/2-1-2/7/o5x x 0 1",
+    "x2-2o/3-3/2---2/7/2---2/3-3/o2-2x x 0 1",
+    "x5o/1-----1/1-3-1/1-1-1-1/1-3-1/1-----1/o5x x 0 1",
+];
+
+fn copymake(pos: &ataxx::Board, depth: u8) -> u64 {
+    if depth == 0 {
+        return 1;
+    } else if pos.is_gameover() {
+        return 0;
+    }
+
+    let moves = pos.pseudolegal_moves();
+    let mut nodes: u64 = 0;
+
+    for mv in &moves {
+        let npos = pos.after_move(mv);
+        nodes += copymake(&npos, depth - 1);
+    }
+
+    nodes
+}
+
+fn makeunmake(pos: &mut ataxx::Board, depth: u8) -> u64 {
+    if depth == 0 {
+        return 1;
+    } else if pos.is_gameover() {
+        return 0;
+    }
+
+    let moves = pos.pseudolegal_moves();
+    let mut nodes: u64 = 0;
+
+    for mv in &moves {
+        let undo = pos.makemove(mv);
+        nodes += makeunmake(pos, depth - 1);
+        pos.unmakemove(mv, &undo);
+    }
+
+    nodes
+}
+
+fn run_copymake() {
+    for fen in FENS {
+        let pos = ataxx::Board::from_fen(fen);
+        std::hint::black_box(copymake(&pos, 3));
+    }
+}
+
+fn run_makeunmake() {
+    for fen in FENS {
+        let mut pos = ataxx::Board::from_fen(fen);
+        std::hint::black_box(makeunmake(&mut pos, 3));
+    }
+}
+
+#[cfg(test)]
+mod makemove {
+    use super::*;
+    use test::Bencher;
+
+    #[bench]
+    fn copymake(b: &mut Bencher) {
+        b.iter(run_copymake);
+    }
+
+    #[bench]
+    fn makeunmake(b: &mut Bencher) {
+        b.iter(run_makeunmake);
+    }
+}
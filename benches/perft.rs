@@ -2,35 +2,9 @@
 
 extern crate test;
 
-use ataxx;
-
-fn run_perft() {
-    let fens: [&str; 20] = [
-        "x5o/7/7/7/7/7/o5x x 0 1",
-        "x5o/7/2-1-2/7/2-1-2/7/o5x x 0 1",
-        "x5o/7/3-3/2-1-2/3-3/7/o5x x 0 1",
-        "x2-2o/3-3/2---2/7/2---2/3-3/o2-2x x 0 1",
-        "x2-2o/3-3/7/--3--/7/3-3/o2-2x x 0 1",
-        "x1-1-1o/2-1-2/2-1-2/7/2-1-2/2-1-2/o1-1-1x x 0 1",
-        "x5o/7/2-1-2/3-3/2-1-2/7/o5x x 0 1",
-        "x5o/7/3-3/2---2/3-3/7/o5x x 0 1",
-        "x5o/2-1-2/1-3-1/7/1-3-1/2-1-2/o5x x 0 1",
-        "x5o/1-3-1/2-1-2/7/2-1-2/1-3-1/o5x x 0 1",
-        "x-1-1-o/-1-1-1-/1-1-1-1/-1-1-1-/1-1-1-1/-1-1-1-/o-1-1-x x 0 1",
-        "x-1-1-o/1-1-1-1/1-1-1-1/1-1-1-1/1-1-1-1/1-1-1-1/o-1-1-x x 0 1",
-        "x1-1-1o/2-1-2/-------/2-1-2/-------/2-1-2/o1-1-1x x 0 1",
-        "x5o/1-----1/1-3-1/1-1-1-1/1-3-1/1-----1/o5x x 0 1",
-        "x-1-1-o/1-1-1-1/-1-1-1-/-1-1-1-/-1-1-1-/1-1-1-1/o-1-1-x/ x 0 1",
-        "x5o/1--1--1/1--1--1/7/1--1--1/1--1--1/o5x x 0 1",
-        "x-3-o/1-1-1-1/1-1-1-1/3-3/1-1-1-1/1-1-1-1/o-3-x x 0 1",
-        "x2-2o/3-3/3-3/-------/3-3/3-3/o2-2x x 0 1",
-        "x2-2o/2-1-2/1-3-1/-2-2-/1-3-1/2-1-2/o2-2x x 0 1",
-        "x5o/6-/1-4-/-3--1/2-4/7/o-3-x x 0 1",
-    ];
-
-    for fen in fens {
-        let pos = ataxx::Board::from_fen(fen);
-        std::hint::black_box(ataxx::perft(&pos, 3));
+fn run_perft(suite: &[ataxx::SuiteEntry]) {
+    for entry in suite {
+        std::hint::black_box(ataxx::perft(&entry.board, 3));
     }
 }
 
@@ -41,6 +15,7 @@ mod perft {
 
     #[bench]
     fn perft(b: &mut Bencher) {
-        b.iter(|| run_perft());
+        let suite = ataxx::standard_suite();
+        b.iter(|| run_perft(&suite));
     }
 }
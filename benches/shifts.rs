@@ -2,17 +2,15 @@
 
 extern crate test;
 
-use ataxx;
-
 fn run_singles() {
-    for sq in 0..49 {
-        std::hint::black_box(ataxx::Bitboard::from_index(sq).singles());
+    for sq in ataxx::Square::ALL {
+        std::hint::black_box(ataxx::Bitboard::from_square(sq).singles());
     }
 }
 
 fn run_doubles() {
-    for sq in 0..49 {
-        std::hint::black_box(ataxx::Bitboard::from_index(sq).doubles());
+    for sq in ataxx::Square::ALL {
+        std::hint::black_box(ataxx::Bitboard::from_square(sq).doubles());
     }
 }
 
@@ -23,11 +21,11 @@ mod shifts {
 
     #[bench]
     fn singles(b: &mut Bencher) {
-        b.iter(|| run_singles());
+        b.iter(run_singles);
     }
 
     #[bench]
     fn doubles(b: &mut Bencher) {
-        b.iter(|| run_doubles());
+        b.iter(run_doubles);
     }
 }
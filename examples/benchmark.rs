@@ -1,38 +1,19 @@
-use ataxx;
 use std::time::Instant;
 
 fn main() {
-    let fens = [
-        "x5o/7/7/7/7/7/o5x x 0 1",
-        "x5o/7/2-1-2/7/2-1-2/7/o5x x 0 1",
-        "x5o/7/3-3/2-1-2/3-3/7/o5x x 0 1",
-        "x2-2o/3-3/2---2/7/2---2/3-3/o2-2x x 0 1",
-        "x2-2o/3-3/7/--3--/7/3-3/o2-2x x 0 1",
-        "x1-1-1o/2-1-2/2-1-2/7/2-1-2/2-1-2/o1-1-1x x 0 1",
-        "x5o/7/2-1-2/3-3/2-1-2/7/o5x x 0 1",
-        "x5o/7/3-3/2---2/3-3/7/o5x x 0 1",
-        "x5o/2-1-2/1-3-1/7/1-3-1/2-1-2/o5x x 0 1",
-        "x5o/1-3-1/2-1-2/7/2-1-2/1-3-1/o5x x 0 1",
-        "x-1-1-o/-1-1-1-/1-1-1-1/-1-1-1-/1-1-1-1/-1-1-1-/o-1-1-x x 0 1",
-        "x-1-1-o/1-1-1-1/1-1-1-1/1-1-1-1/1-1-1-1/1-1-1-1/o-1-1-x x 0 1",
-        "x1-1-1o/2-1-2/-------/2-1-2/-------/2-1-2/o1-1-1x x 0 1",
-        "x5o/1-----1/1-3-1/1-1-1-1/1-3-1/1-----1/o5x x 0 1",
-        "x-1-1-o/1-1-1-1/-1-1-1-/-1-1-1-/-1-1-1-/1-1-1-1/o-1-1-x/ x 0 1",
-        "x5o/1--1--1/1--1--1/7/1--1--1/1--1--1/o5x x 0 1",
-        "x-3-o/1-1-1-1/1-1-1-1/3-3/1-1-1-1/1-1-1-1/o-3-x x 0 1",
-        "x2-2o/3-3/3-3/-------/3-3/3-3/o2-2x x 0 1",
-        "x2-2o/2-1-2/1-3-1/-2-2-/1-3-1/2-1-2/o2-2x x 0 1",
-        "x5o/6-/1-4-/-3--1/2-4/7/o-3-x x 0 1",
-    ];
+    let suite = ataxx::standard_suite();
     let depth = 7;
+    let threads = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(1);
     let mut total_nodes = 0;
     let mut total_time = std::time::Duration::new(0, 0);
 
     println!("Pos       Nodes       ΣNodes     Time     ΣTime   Mnps  ΣMnps  FEN");
-    for (idx, fen) in fens.iter().enumerate() {
-        let pos = ataxx::Board::from_fen(fen);
+    for (idx, entry) in suite.iter().enumerate() {
         let start = Instant::now();
-        let nodes = ataxx::perft(&pos, depth);
+        let nodes = ataxx::perft_parallel(&entry.board, depth, threads);
         let duration = start.elapsed();
 
         total_nodes += nodes;
@@ -50,7 +31,7 @@ fn main() {
             total_time.as_millis() as f64 / 1000.0,
             (nps / 1_000_000f64) as u64,
             (total_nps / 1_000_000f64) as u64,
-            fen
+            entry.fen
         );
     }
 }
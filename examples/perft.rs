@@ -1,4 +1,3 @@
-use ataxx;
 use std::time::Instant;
 
 fn main() {
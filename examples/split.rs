@@ -1,28 +1,17 @@
-use ataxx;
 use std::time::Instant;
 
 fn main() {
     let pos = ataxx::Board::from_fen("startpos");
-    let depth = 6;
-    let moves = pos.legal_moves();
+    let depth = 7;
     let start = Instant::now();
-    let mut total: u64 = 0;
 
     print!("{}", pos);
 
-    for mv in &moves {
-        let npos = pos.after_move(&mv);
-        let nodes: u64 = ataxx::perft(&npos, depth);
-        total += nodes;
-        println!("{} {}", &mv, nodes);
-    }
-
-    if moves.is_empty() {
-        let npos = pos.after_pass();
-        let nodes: u64 = ataxx::perft(&npos, depth);
-        total += nodes;
-        println!("0000 {}", nodes);
+    let moves = ataxx::perft_divide(&pos, depth);
+    for (mv, nodes) in &moves {
+        println!("{} {}", mv, nodes);
     }
+    let total: u64 = moves.iter().map(|(_, nodes)| nodes).sum();
 
     let duration = start.elapsed();
     let nps = total as f64 / duration.as_secs_f64();
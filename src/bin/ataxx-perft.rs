@@ -0,0 +1,173 @@
+use ataxx::{
+    parse_suite, perft, perft_divide, perft_hashed, run_suite_with, Board, PerftTable, SuiteEntry,
+    STANDARD_SUITE,
+};
+use std::process::ExitCode;
+use std::time::Instant;
+
+const USAGE: &str =
+    "usage: ataxx-perft [--fen <fen>] [--depth <n>] [--divide] [--expect <nodes>] [--hash <mb>]
+       ataxx-perft --suite <file|standard> [--depth <n>] [--hash <mb>]";
+
+struct Options {
+    fen: String,
+    depth: u8,
+    divide: bool,
+    expect: Option<u64>,
+    hash: Option<usize>,
+    suite: Option<String>,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        fen: "startpos".to_string(),
+        depth: 5,
+        divide: false,
+        expect: None,
+        hash: None,
+        suite: None,
+    };
+    let mut args = args.iter();
+
+    fn value<'a>(name: &str, arg: Option<&'a String>) -> Result<&'a str, String> {
+        arg.map(String::as_str)
+            .ok_or_else(|| format!("missing value for {}", name))
+    }
+
+    fn number<T: std::str::FromStr>(name: &str, arg: Option<&String>) -> Result<T, String> {
+        let text = value(name, arg)?;
+        text.parse()
+            .map_err(|_| format!("invalid value '{}' for {}", text, name))
+    }
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--fen" => options.fen = value(arg, args.next())?.to_string(),
+            "--depth" => options.depth = number(arg, args.next())?,
+            "--divide" => options.divide = true,
+            "--expect" => options.expect = Some(number(arg, args.next())?),
+            "--hash" => options.hash = Some(number(arg, args.next())?),
+            "--suite" => options.suite = Some(value(arg, args.next())?.to_string()),
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Loads a suite file, "standard" is the built-in suite
+fn load_suite(name: &str) -> Result<Vec<SuiteEntry>, String> {
+    let text = if name == "standard" {
+        STANDARD_SUITE.to_string()
+    } else {
+        std::fs::read_to_string(name).map_err(|e| format!("{}: {}", name, e))?
+    };
+    parse_suite(&text).map_err(|e| format!("{}: {}", name, e))
+}
+
+/// Checks every count in the suite up to the depth given, failures are printed as they are found
+fn run_suite_file(name: &str, options: &Options) -> ExitCode {
+    let suite = match load_suite(name) {
+        Ok(suite) => suite,
+        Err(e) => {
+            eprintln!("invalid suite {}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let table = options.hash.map(PerftTable::new);
+    let start = Instant::now();
+    let report = run_suite_with(&suite, options.depth, |pos, depth| match &table {
+        Some(table) => perft_hashed(pos, depth, table).nodes,
+        None => perft(pos, depth),
+    });
+    let duration = start.elapsed();
+
+    for failure in &report.failures {
+        println!("fail {}", failure);
+    }
+    println!(
+        "positions {} checks {} failures {} nodes {} time {} nps {}",
+        report.positions,
+        report.checks,
+        report.failures.len(),
+        report.nodes,
+        duration.as_millis(),
+        (report.nodes as f64 / duration.as_secs_f64()) as u64
+    );
+
+    if report.passed() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    if let Some(suite) = &options.suite {
+        return run_suite_file(suite, &options);
+    }
+
+    let pos = match Board::try_from_fen(&options.fen) {
+        Ok(pos) => pos,
+        Err(e) => {
+            eprintln!("invalid fen: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let table = options.hash.map(PerftTable::new);
+    let depth = options.depth;
+
+    let start = Instant::now();
+    let nodes = if options.divide {
+        let divide = match &table {
+            Some(table) if depth > 0 => pos
+                .legal_moves()
+                .iter()
+                .map(|mv| {
+                    (
+                        *mv,
+                        perft_hashed(&pos.after_move(mv), depth - 1, table).nodes,
+                    )
+                })
+                .collect(),
+            _ => perft_divide(&pos, depth),
+        };
+
+        for (mv, nodes) in &divide {
+            println!("{} {}", mv, nodes);
+        }
+        divide.iter().map(|(_, nodes)| nodes).sum()
+    } else {
+        match &table {
+            Some(table) => perft_hashed(&pos, depth, table).nodes,
+            None => perft(&pos, depth),
+        }
+    };
+    let duration = start.elapsed();
+
+    println!(
+        "nodes {} time {} nps {}",
+        nodes,
+        duration.as_millis(),
+        (nodes as f64 / duration.as_secs_f64()) as u64
+    );
+
+    match options.expect {
+        Some(expected) if expected != nodes => {
+            eprintln!("mismatch: expected {} nodes, got {}", expected, nodes);
+            ExitCode::FAILURE
+        }
+        _ => ExitCode::SUCCESS,
+    }
+}
@@ -0,0 +1,101 @@
+//! A UAI engine playing random legal moves, used to test the match runner
+//!
+//! `--seed <n>` fixes the moves played. `--crash-after <n>` exits and `--hang-after <n>`
+//! stops responding on the nth "go", so broken engines can be tested too.
+
+use ataxx::{Board, Game, Rng};
+use std::io::{self, BufRead};
+use std::time::Duration;
+
+const USAGE: &str = "usage: ataxx-random [--seed <n>] [--crash-after <n>] [--hang-after <n>]";
+
+#[derive(Default)]
+struct Options {
+    seed: Option<u64>,
+    crash_after: Option<u32>,
+    hang_after: Option<u32>,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.iter();
+
+    fn number<T: std::str::FromStr>(name: &str, arg: Option<&String>) -> Result<T, String> {
+        let text = arg.ok_or_else(|| format!("missing value for {}", name))?;
+        text.parse()
+            .map_err(|_| format!("invalid value '{}' for {}", text, name))
+    }
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => options.seed = Some(number(arg, args.next())?),
+            "--crash-after" => options.crash_after = Some(number(arg, args.next())?),
+            "--hang-after" => options.hang_after = Some(number(arg, args.next())?),
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+
+    Ok(options)
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let mut rng = options.seed.map_or_else(Rng::from_time, Rng::new);
+    let mut game = Game::new(Board::from_fen("startpos"));
+    let mut searches = 0;
+
+    for line in io::stdin().lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break,
+        };
+        let line = line.trim();
+        let (command, args) = line.split_once(' ').unwrap_or((line, ""));
+
+        match command {
+            "uai" => {
+                println!("id name Random");
+                println!("id author kz04px");
+                println!("uaiok");
+            }
+            "isready" => println!("readyok"),
+            "uainewgame" => game = Game::new(Board::from_fen("startpos")),
+            "position" => match args.trim().parse::<Game>() {
+                Ok(new) => game = new,
+                Err(e) => {
+                    println!("info string {}", e);
+                    game = Game::new(Board::from_fen("startpos"));
+                }
+            },
+            "go" => {
+                searches += 1;
+                if options.crash_after == Some(searches) {
+                    std::process::exit(1);
+                }
+                if options.hang_after == Some(searches) {
+                    loop {
+                        std::thread::sleep(Duration::from_secs(60));
+                    }
+                }
+
+                let moves = game.board().legal_moves();
+                if game.board().is_gameover() || moves.is_empty() {
+                    println!("bestmove 0000");
+                } else {
+                    println!("bestmove {}", moves[rng.below(moves.len())]);
+                }
+            }
+            "stop" | "setoption" | "" => {}
+            "quit" => break,
+            _ => println!("info string unknown command '{}'", command),
+        }
+    }
+}
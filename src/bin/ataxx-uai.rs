@@ -0,0 +1,270 @@
+use ataxx::{
+    search, search_threads, standard_suite, win_distance, Board, Clocks, Game, SearchLimits,
+    SearchResult, TranspositionTable, DEFAULT_HASH_MB,
+};
+use std::io::{self, BufRead};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+const BENCH_DEPTH: u8 = 5;
+
+const MAX_THREADS: usize = 256;
+
+struct Engine {
+    game: Game,
+    tt: Arc<TranspositionTable>,
+    threads: usize,
+    stop: Arc<AtomicBool>,
+    searching: Option<JoinHandle<()>>,
+}
+
+impl Engine {
+    fn new() -> Self {
+        Engine {
+            game: Game::new(Board::from_fen("startpos")),
+            tt: Arc::new(TranspositionTable::new(DEFAULT_HASH_MB)),
+            threads: 1,
+            stop: Arc::new(AtomicBool::new(false)),
+            searching: None,
+        }
+    }
+
+    fn uai(&self) {
+        println!("id name Rustaxx");
+        println!("id author kz04px");
+        println!(
+            "option name Hash type spin default {} min 1 max 65536",
+            DEFAULT_HASH_MB
+        );
+        println!(
+            "option name Threads type spin default 1 min 1 max {}",
+            MAX_THREADS
+        );
+        println!("uaiok");
+    }
+
+    fn uainewgame(&mut self) {
+        self.stop();
+        self.game = Game::new(Board::from_fen("startpos"));
+        match Arc::get_mut(&mut self.tt) {
+            Some(tt) => tt.clear(),
+            None => self.tt = Arc::new(TranspositionTable::new(DEFAULT_HASH_MB)),
+        }
+    }
+
+    /// Handles "position startpos moves ..." and "position fen <fen> moves ..."
+    ///
+    /// The moves only have to be legal on the board, the GUI decides when the game is over.
+    /// A position that can't be set resets the game rather than keeping the old one.
+    fn position(&mut self, args: &str) {
+        self.stop();
+        match args.parse::<Game>() {
+            Ok(game) => self.game = game,
+            Err(e) => {
+                println!("info string {}", e);
+                self.game = Game::new(Board::from_fen("startpos"));
+            }
+        }
+    }
+
+    /// Handles "setoption name <name> value <value>"
+    fn setoption(&mut self, args: &str) {
+        self.stop();
+
+        let (name, value) = match args.strip_prefix("name ") {
+            Some(rest) => match rest.split_once(" value ") {
+                Some((name, value)) => (name.trim(), value.trim()),
+                None => (rest.trim(), ""),
+            },
+            None => {
+                println!("info string expected 'setoption name <name> value <value>'");
+                return;
+            }
+        };
+
+        match (name.to_ascii_lowercase().as_str(), value.parse::<usize>()) {
+            ("hash", Ok(mb)) if (1..=65536).contains(&mb) => {
+                self.tt = Arc::new(TranspositionTable::new(mb));
+            }
+            ("threads", Ok(threads)) if (1..=MAX_THREADS).contains(&threads) => {
+                self.threads = threads;
+            }
+            _ => println!("info string unknown option '{}' value '{}'", name, value),
+        }
+    }
+
+    fn go(&mut self, args: &str) {
+        self.stop();
+
+        let limits = match self.parse_go(args) {
+            Ok(limits) => limits,
+            Err(e) => {
+                // The GUI is waiting for a move whatever happens
+                println!("info string {}", e);
+                println!("bestmove 0000");
+                return;
+            }
+        };
+
+        let pos = *self.game.board();
+        let tt = Arc::clone(&self.tt);
+        let threads = self.threads;
+        self.stop = Arc::clone(&limits.stop);
+
+        self.searching = Some(std::thread::spawn(move || {
+            let result = search_threads(&pos, &limits, &tt, threads, print_info);
+
+            // An infinite search only reports its move once told to stop
+            while limits.infinite && !limits.stop.load(Ordering::Relaxed) {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            match result.best_move {
+                Some(mv) => println!("bestmove {}", mv),
+                None => println!("bestmove 0000"),
+            }
+        }));
+    }
+
+    fn parse_go(&self, args: &str) -> Result<SearchLimits, String> {
+        let pos = self.game.board();
+        let mut limits = SearchLimits::default();
+        let mut clocks = Clocks::default();
+        let mut clocked = false;
+        let mut tokens = args.split_whitespace();
+
+        fn number<T: std::str::FromStr>(name: &str, token: Option<&str>) -> Result<T, String> {
+            token
+                .and_then(|t| t.parse().ok())
+                .ok_or_else(|| format!("expected a number after '{}'", name))
+        }
+
+        // Clock values can be negative when a GUI lets the engine overrun slightly
+        let millis = |name: &str, token: Option<&str>| -> Result<Duration, String> {
+            let ms: i64 = number(name, token)?;
+            Ok(Duration::from_millis(ms.max(0) as u64))
+        };
+
+        while let Some(token) = tokens.next() {
+            match token {
+                "btime" => {
+                    clocks.btime = millis(token, tokens.next())?;
+                    clocked = true;
+                }
+                "wtime" => {
+                    clocks.wtime = millis(token, tokens.next())?;
+                    clocked = true;
+                }
+                "binc" => clocks.binc = millis(token, tokens.next())?,
+                "winc" => clocks.winc = millis(token, tokens.next())?,
+                "movestogo" => clocks.movestogo = Some(number(token, tokens.next())?),
+                "depth" => limits.depth = Some(number(token, tokens.next())?),
+                "nodes" => limits.nodes = Some(number(token, tokens.next())?),
+                "movetime" => limits.movetime = Some(millis(token, tokens.next())?),
+                "infinite" => limits.infinite = true,
+                "searchmoves" => {
+                    for text in tokens.by_ref() {
+                        let mv = pos.parse_move(text).map_err(|e| e.to_string())?;
+                        limits.searchmoves.push(mv);
+                    }
+                }
+                _ => return Err(format!("unknown go parameter '{}'", token)),
+            }
+        }
+
+        if clocked {
+            limits.clocks = Some(clocks);
+        }
+
+        Ok(limits)
+    }
+
+    fn stop(&mut self) {
+        if let Some(handle) = self.searching.take() {
+            self.stop.store(true, Ordering::Relaxed);
+            handle.join().expect("search thread panicked");
+        }
+    }
+}
+
+fn print_info(result: &SearchResult) {
+    let score = match win_distance(result.score) {
+        Some(plies) => format!("mate {}", plies.signum() * (plies.abs() + 1) / 2),
+        None => format!("cp {}", result.score),
+    };
+    let millis = result.time.as_millis();
+    let nps = result.nodes as u128 * 1000 / millis.max(1);
+    let pv: Vec<String> = result.pv.iter().map(|mv| mv.to_string()).collect();
+
+    println!(
+        "info depth {} score {} nodes {} time {} nps {} hashfull {} pv {}",
+        result.depth,
+        score,
+        result.nodes,
+        millis,
+        nps,
+        result.hashfull,
+        pv.join(" ")
+    );
+}
+
+/// Fixed depth searches of the standard layouts, the node total is a signature of the search
+fn bench(depth: u8) {
+    let limits = SearchLimits {
+        depth: Some(depth),
+        ..Default::default()
+    };
+    let start = Instant::now();
+    let mut nodes = 0;
+
+    for entry in standard_suite() {
+        let result = search(&entry.board, &limits);
+        nodes += result.nodes;
+    }
+
+    let millis = start.elapsed().as_millis();
+    println!(
+        "nodes {} time {} nps {}",
+        nodes,
+        millis,
+        nodes as u128 * 1000 / millis.max(1)
+    );
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("bench") {
+        let depth = args.get(2).and_then(|d| d.parse().ok());
+        bench(depth.unwrap_or(BENCH_DEPTH));
+        return;
+    }
+
+    let mut engine = Engine::new();
+
+    for line in io::stdin().lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break,
+        };
+        let line = line.trim();
+        let (command, args) = line.split_once(' ').unwrap_or((line, ""));
+
+        match command {
+            "uai" => engine.uai(),
+            "isready" => println!("readyok"),
+            "uainewgame" => engine.uainewgame(),
+            "position" => engine.position(args.trim()),
+            "setoption" => engine.setoption(args.trim()),
+            "go" => engine.go(args),
+            "stop" => engine.stop(),
+            "quit" => break,
+            "bench" => bench(args.trim().parse().unwrap_or(BENCH_DEPTH)),
+            "d" => print!("{}", engine.game.board()),
+            "" => {}
+            _ => println!("info string unknown command '{}'", command),
+        }
+    }
+
+    engine.stop();
+}
@@ -1,3 +1,5 @@
+use crate::squares::Square;
+use crate::symmetry::Transform;
 use std::fmt;
 use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
 
@@ -9,8 +11,8 @@ pub struct BitboardIter(u64);
 
 impl Bitboard {
     #[must_use]
-    pub const fn from_index(sq: u8) -> Self {
-        Self(1u64 << sq)
+    pub const fn from_square(sq: Square) -> Self {
+        Self(1u64 << sq as u8)
     }
 
     #[must_use]
@@ -24,41 +26,48 @@ impl Bitboard {
     }
 }
 
+impl BitboardIter {
+    /// Bits outside the 7x7 board are ignored
+    #[must_use]
+    pub const fn new(bb: Bitboard) -> Self {
+        Self(bb.0 & 0x1ffffffffffff)
+    }
+}
+
 impl Iterator for BitboardIter {
-    type Item = u8;
+    type Item = Square;
 
-    fn next(&mut self) -> Option<u8> {
+    fn next(&mut self) -> Option<Square> {
         if self.0 == 0 {
             None
         } else {
-            let index = self.0.trailing_zeros() as u8;
+            let index = self.0.trailing_zeros() as usize;
             self.0 &= self.0 - 1;
-            Some(index)
+            Some(Square::ALL[index])
         }
     }
 }
 
 impl IntoIterator for Bitboard {
-    type Item = u8;
+    type Item = Square;
     type IntoIter = BitboardIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        BitboardIter(self.0)
+        BitboardIter::new(self)
     }
 }
 
 impl fmt::Display for Bitboard {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for y in (0..7).rev() {
-            for x in 0..7 {
-                let idx = 7 * y + x;
-                if self.is_set(idx) {
+        for rank in (0..7).rev() {
+            for file in 0..7 {
+                if self.is_set(Square::ALL[7 * rank + file]) {
                     write!(f, "1")?;
                 } else {
                     write!(f, "0")?;
                 }
             }
-            writeln!(f, "")?;
+            writeln!(f)?;
         }
 
         Ok(())
@@ -68,7 +77,6 @@ impl fmt::Display for Bitboard {
 impl BitAnd for Bitboard {
     type Output = Self;
 
-    #[must_use]
     fn bitand(self, rhs: Self) -> Self {
         Self(self.0 & rhs.0)
     }
@@ -77,7 +85,6 @@ impl BitAnd for Bitboard {
 impl Not for Bitboard {
     type Output = Self;
 
-    #[must_use]
     fn not(self) -> Self {
         Self(!self.0 & 0x1ffffffffffff)
     }
@@ -104,7 +111,6 @@ impl BitXorAssign for Bitboard {
 impl BitXor for Bitboard {
     type Output = Bitboard;
 
-    #[must_use]
     fn bitxor(self, rhs: Bitboard) -> Bitboard {
         Bitboard(self.0 ^ rhs.0)
     }
@@ -113,7 +119,6 @@ impl BitXor for Bitboard {
 impl BitOr for Bitboard {
     type Output = Bitboard;
 
-    #[must_use]
     fn bitor(self, rhs: Bitboard) -> Bitboard {
         Bitboard(self.0 | rhs.0)
     }
@@ -136,8 +141,8 @@ impl Bitboard {
     }
 
     #[must_use]
-    pub const fn is_set(&self, idx: u8) -> bool {
-        ((self.0 >> idx) & 1) == 1
+    pub const fn is_set(&self, sq: Square) -> bool {
+        ((self.0 >> sq as u8) & 1) == 1
     }
 
     #[must_use]
@@ -199,6 +204,63 @@ impl Bitboard {
     pub const fn count(&self) -> i32 {
         self.0.count_ones() as i32
     }
+
+    #[must_use]
+    pub fn transform(&self, t: Transform) -> Self {
+        let mut bb = Bitboard(0);
+        for sq in *self {
+            bb |= Bitboard::from_square(sq.transform(t));
+        }
+        bb
+    }
+
+    #[must_use]
+    pub const fn flip_vertical(&self) -> Self {
+        let mut bb = 0;
+        let mut rank = 0;
+        while rank < 7 {
+            bb |= ((self.0 >> (7 * rank)) & 0x7f) << (7 * (6 - rank));
+            rank += 1;
+        }
+        Self(bb)
+    }
+
+    #[must_use]
+    pub const fn flip_horizontal(&self) -> Self {
+        let mut bb = 0;
+        let mut rank = 0;
+        while rank < 7 {
+            let row = ((self.0 >> (7 * rank)) & 0x7f) as u8;
+            bb |= ((row.reverse_bits() >> 1) as u64) << (7 * rank);
+            rank += 1;
+        }
+        Self(bb)
+    }
+
+    #[must_use]
+    pub fn flip_diagonal(&self) -> Self {
+        self.transform(Transform::FlipDiagonal)
+    }
+
+    #[must_use]
+    pub fn flip_anti_diagonal(&self) -> Self {
+        self.transform(Transform::FlipAntiDiagonal)
+    }
+
+    #[must_use]
+    pub fn rotate_90(&self) -> Self {
+        self.flip_diagonal().flip_vertical()
+    }
+
+    #[must_use]
+    pub const fn rotate_180(&self) -> Self {
+        self.flip_vertical().flip_horizontal()
+    }
+
+    #[must_use]
+    pub fn rotate_270(&self) -> Self {
+        self.flip_diagonal().flip_horizontal()
+    }
 }
 
 #[cfg(test)]
@@ -288,4 +350,50 @@ mod tests {
         assert_eq!(crate::Bitboard(0x1).doubles(), crate::Bitboard(0x1c204));
         assert_eq!(crate::Bitboard(0x100).doubles(), crate::Bitboard(0x1e20408));
     }
+
+    #[test]
+    fn flips() {
+        use crate::Square;
+
+        let bb = |sqs: &[Square]| {
+            sqs.iter().fold(crate::Bitboard(0), |bb, &sq| {
+                bb | crate::Bitboard::from_square(sq)
+            })
+        };
+        let l = bb(&[Square::A1, Square::B1, Square::C1, Square::A2]);
+
+        assert_eq!(
+            l.flip_vertical(),
+            bb(&[Square::A7, Square::B7, Square::C7, Square::A6])
+        );
+        assert_eq!(
+            l.flip_horizontal(),
+            bb(&[Square::G1, Square::F1, Square::E1, Square::G2])
+        );
+        assert_eq!(
+            l.flip_diagonal(),
+            bb(&[Square::A1, Square::A2, Square::A3, Square::B1])
+        );
+        assert_eq!(
+            l.flip_anti_diagonal(),
+            bb(&[Square::G7, Square::G6, Square::G5, Square::F7])
+        );
+        assert_eq!(
+            l.rotate_90(),
+            bb(&[Square::A7, Square::A6, Square::A5, Square::B7])
+        );
+        assert_eq!(
+            l.rotate_180(),
+            bb(&[Square::G7, Square::F7, Square::E7, Square::G6])
+        );
+        assert_eq!(
+            l.rotate_270(),
+            bb(&[Square::G1, Square::G2, Square::G3, Square::F1])
+        );
+
+        let full = crate::Bitboard::full();
+        assert_eq!(full.flip_vertical(), full);
+        assert_eq!(full.flip_horizontal(), full);
+        assert_eq!(full.rotate_90(), full);
+    }
 }
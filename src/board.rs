@@ -1,15 +1,37 @@
 use crate::bitboard::*;
+use crate::builder::BoardError;
 use crate::colour::*;
-use crate::moves::Move;
+use crate::fen::FenError;
+use crate::movelist::MoveList;
+use crate::moves::{Move, MoveParseError};
+use crate::result::*;
+use crate::squares::Square;
+use crate::symmetry::Transform;
+use crate::zobrist::*;
 use crate::LUT_DOUBLES;
 use std::fmt;
+use std::str::FromStr;
 
+/// An Ataxx position
+///
+/// The fields are private so the Zobrist key kept in `hash` always matches the
+/// pieces and side to move, build positions with `new`, a FEN or `BoardBuilder`.
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub struct Board {
-    pub pieces: [Bitboard; 3],
-    pub turn: Colour,
+    pieces: [Bitboard; 3],
+    turn: Colour,
+    halfmoves: u32,
+    fullmoves: u32,
+    hash: u64,
+}
+
+/// Everything `unmakemove` needs that cannot be recovered from the move itself
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Undo {
+    pub captured: Bitboard,
     pub halfmoves: u32,
     pub fullmoves: u32,
+    pub turn: Colour,
 }
 
 impl fmt::Display for Board {
@@ -20,8 +42,8 @@ impl fmt::Display for Board {
         loop {
             let mut yeet = true;
             for x in 0..7 {
-                let idx = 7 * height + x;
-                if !self.blockers().is_set(idx) {
+                let sq = Square::ALL[7 * height + x];
+                if !self.blockers().is_set(sq) {
                     yeet = false;
                     break;
                 }
@@ -35,8 +57,8 @@ impl fmt::Display for Board {
         loop {
             let mut yeet = true;
             for y in 0..7 {
-                let idx = 7 * y + width;
-                if !self.blockers().is_set(idx) {
+                let sq = Square::ALL[7 * y + width];
+                if !self.blockers().is_set(sq) {
                     yeet = false;
                     break;
                 }
@@ -49,18 +71,18 @@ impl fmt::Display for Board {
 
         for y in (0..=height).rev() {
             for x in 0..=width {
-                let idx = 7 * y + x;
-                if self.black().is_set(idx) {
+                let sq = Square::ALL[7 * y + x];
+                if self.black().is_set(sq) {
                     write!(f, "x")?;
-                } else if self.white().is_set(idx) {
+                } else if self.white().is_set(sq) {
                     write!(f, "o")?;
-                } else if self.blockers().is_set(idx) {
+                } else if self.blockers().is_set(sq) {
                     write!(f, " ")?;
                 } else {
                     write!(f, "-")?;
                 }
             }
-            writeln!(f, "")?;
+            writeln!(f)?;
         }
 
         match self.turn {
@@ -73,6 +95,20 @@ impl fmt::Display for Board {
 }
 
 impl Board {
+    /// Creates a position from raw bitboards, the hash is computed from scratch
+    #[must_use]
+    pub const fn new(pieces: [Bitboard; 3], turn: Colour, halfmoves: u32, fullmoves: u32) -> Self {
+        let mut pos = Board {
+            pieces,
+            turn,
+            halfmoves,
+            fullmoves,
+            hash: 0,
+        };
+        pos.hash = pos.calculate_hash();
+        pos
+    }
+
     #[must_use]
     pub fn after_move(&self, mv: &Move) -> Self {
         let mut npos = *self;
@@ -89,8 +125,15 @@ impl Board {
 
     #[must_use]
     pub fn from_fen(fen: &str) -> Self {
+        match Board::try_from_fen(fen) {
+            Ok(pos) => pos,
+            Err(e) => panic!("Invalid FEN: {}", e),
+        }
+    }
+
+    pub fn try_from_fen(fen: &str) -> Result<Self, FenError> {
         if fen == "startpos" {
-            return Board::from_fen("x5o/7/7/7/7/7/o5x x 0 1");
+            return Board::try_from_fen("x5o/7/7/7/7/7/o5x x 0 1");
         }
 
         let mut pos = Board {
@@ -98,55 +141,77 @@ impl Board {
             turn: Colour::Black,
             halfmoves: 0,
             fullmoves: 1,
+            hash: 0,
         };
-        let parts: Vec<&str> = fen.split(' ').collect();
+        let parts: Vec<&str> = fen.split_whitespace().collect();
 
         if parts.len() != 4 {
-            panic!("FEN wrong format");
+            return Err(FenError::WrongFieldCount(parts.len()));
         }
 
-        let mut x: i32 = 0;
-        let mut y: i32 = 6;
-        for c in parts[0].chars() {
-            match c {
-                'x' | 'X' => {
-                    pos.pieces[0] ^= crate::Bitboard::from_index((7 * y + x) as u8);
-                    x += 1;
-                }
-                'o' | 'O' => {
-                    pos.pieces[1] ^= crate::Bitboard::from_index((7 * y + x) as u8);
-                    x += 1;
-                }
-                '-' => {
-                    pos.pieces[2] ^= crate::Bitboard::from_index((7 * y + x) as u8);
-                    x += 1;
-                }
-                '1'..='7' => x += (c as u8 - b'0') as i32,
-                '/' => {
-                    x = 0;
-                    y -= 1;
-                }
-                _ => panic!("Unrecognised FEN token"),
+        let ranks: Vec<&str> = parts[0].split('/').collect();
+
+        if ranks.len() > 7 {
+            return Err(FenError::TooManyRanks);
+        } else if ranks.len() < 7 {
+            return Err(FenError::TooFewRanks(ranks.len()));
+        }
+
+        for (y, rank) in (0..7u8).rev().zip(ranks) {
+            let mut x: u8 = 0;
+            for c in rank.chars() {
+                let idx = match c {
+                    'x' | 'X' => 0,
+                    'o' | 'O' => 1,
+                    '-' => 2,
+                    '1'..='7' => {
+                        x += c as u8 - b'0';
+                        if x > 7 {
+                            return Err(FenError::RankTooLong { rank: y + 1 });
+                        }
+                        continue;
+                    }
+                    _ => {
+                        return Err(FenError::InvalidPiece {
+                            rank: y + 1,
+                            token: c,
+                        })
+                    }
+                };
+
+                let sq = match Square::from_coords(x, y) {
+                    Some(sq) => sq,
+                    None => return Err(FenError::RankTooLong { rank: y + 1 }),
+                };
+
+                pos.pieces[idx] ^= Bitboard::from_square(sq);
+                x += 1;
+            }
+
+            if x < 7 {
+                return Err(FenError::RankTooShort { rank: y + 1 });
             }
         }
 
         match parts[1] {
-            "x" | "X" => pos.turn = crate::Colour::Black,
-            "o" | "O" => pos.turn = crate::Colour::White,
-            _ => panic!("Unrecognised FEN token"),
+            "x" | "X" => pos.turn = Colour::Black,
+            "o" | "O" => pos.turn = Colour::White,
+            _ => return Err(FenError::InvalidTurn(parts[1].to_string())),
         }
 
         match parts[2].parse::<u32>() {
             Ok(n) => pos.halfmoves = n,
-            Err(_e) => panic!("Unrecognised FEN token"),
+            Err(_e) => return Err(FenError::InvalidHalfmoves(parts[2].to_string())),
         }
 
         match parts[3].parse::<u32>() {
-            Ok(n) => pos.fullmoves = n,
-            Err(_e) => panic!("Unrecognised FEN token"),
+            Ok(n) if n > 0 => pos.fullmoves = n,
+            _ => return Err(FenError::InvalidFullmoves(parts[3].to_string())),
         }
 
-        pos
+        pos.hash = pos.calculate_hash();
+
+        Ok(pos)
     }
 
     #[must_use]
@@ -157,8 +222,7 @@ impl Board {
             let mut spaces = 0;
 
             for x in 0..=6 {
-                let sq: u8 = 7 * y + x;
-                let bb: Bitboard = Bitboard::from_index(sq);
+                let bb: Bitboard = Bitboard::from_square(Square::ALL[7 * y + x]);
 
                 if !(self.pieces[0] & bb).is_empty() {
                     if spaces > 0 {
@@ -207,6 +271,33 @@ impl Board {
         fen
     }
 
+    #[must_use]
+    pub const fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Computes the Zobrist key without relying on the incrementally updated value
+    #[must_use]
+    pub const fn calculate_hash(&self) -> u64 {
+        let mut hash = 0;
+        let mut i = 0;
+
+        while i < 3 {
+            let mut bb = self.pieces[i].0 & Bitboard::full().0;
+            while bb != 0 {
+                hash ^= ZOBRIST_PIECES[i][bb.trailing_zeros() as usize];
+                bb &= bb - 1;
+            }
+            i += 1;
+        }
+
+        if let Colour::White = self.turn {
+            hash ^= ZOBRIST_TURN;
+        }
+
+        hash
+    }
+
     #[must_use]
     pub fn is_gameover(&self) -> bool {
         self.black().is_empty()
@@ -215,6 +306,59 @@ impl Board {
             || (self.both().reach() & self.empty()).is_empty()
     }
 
+    #[must_use]
+    pub fn result(&self) -> Option<GameResult> {
+        let black = self.black().count();
+        let white = self.white().count();
+
+        if black == 0 && white == 0 {
+            Some(GameResult::new(Outcome::Draw, Termination::NoPieces))
+        } else if white == 0 {
+            Some(GameResult::new(Outcome::BlackWin, Termination::NoPieces))
+        } else if black == 0 {
+            Some(GameResult::new(Outcome::WhiteWin, Termination::NoPieces))
+        } else if self.halfmoves >= 100 {
+            Some(GameResult::new(Outcome::Draw, Termination::HalfmoveLimit))
+        } else if (self.both().reach() & self.empty()).is_empty() {
+            let outcome = match black.cmp(&white) {
+                std::cmp::Ordering::Greater => Outcome::BlackWin,
+                std::cmp::Ordering::Less => Outcome::WhiteWin,
+                std::cmp::Ordering::Equal => Outcome::Draw,
+            };
+            Some(GameResult::new(outcome, Termination::NoMoves))
+        } else {
+            None
+        }
+    }
+
+    /// Piece count difference from the point of view of the side to move
+    #[must_use]
+    pub fn material(&self) -> i32 {
+        self.us().count() - self.them().count()
+    }
+
+    /// Black pieces, white pieces and blockers
+    #[must_use]
+    pub const fn pieces(&self) -> [Bitboard; 3] {
+        self.pieces
+    }
+
+    #[must_use]
+    pub const fn turn(&self) -> Colour {
+        self.turn
+    }
+
+    /// Moves since the last single move, the game is drawn at 100
+    #[must_use]
+    pub const fn halfmoves(&self) -> u32 {
+        self.halfmoves
+    }
+
+    #[must_use]
+    pub const fn fullmoves(&self) -> u32 {
+        self.fullmoves
+    }
+
     #[must_use]
     pub const fn black(&self) -> Bitboard {
         self.pieces[Colour::Black as usize]
@@ -255,72 +399,130 @@ impl Board {
         self.pieces[!self.turn as usize]
     }
 
-    pub fn makemove(&mut self, mv: &crate::Move) {
+    pub fn makemove(&mut self, mv: &crate::Move) -> Undo {
+        let undo = Undo {
+            captured: match mv {
+                Move::Pass => Bitboard(0),
+                Move::Drop(sq) | Move::Jump(_, sq) => {
+                    crate::Bitboard::from_square(*sq).singles() & self.them()
+                }
+            },
+            halfmoves: self.halfmoves,
+            fullmoves: self.fullmoves,
+            turn: self.turn,
+        };
+
+        self.toggle_move(mv, undo.captured);
+
         match mv {
-            Move::Pass => {
-                self.halfmoves += 1;
-                self.fullmoves += (self.turn == Colour::White) as u32;
-                self.turn = !self.turn;
-            }
+            Move::Drop(_) => self.halfmoves = 0,
+            _ => self.halfmoves += 1,
+        }
+        self.fullmoves += (self.turn == Colour::White) as u32;
+        self.turn = !self.turn;
+
+        undo
+    }
+
+    /// Takes back a move previously made with `makemove`, using the record it returned
+    pub fn unmakemove(&mut self, mv: &crate::Move, undo: &Undo) {
+        self.turn = undo.turn;
+        self.halfmoves = undo.halfmoves;
+        self.fullmoves = undo.fullmoves;
+
+        self.toggle_move(mv, undo.captured);
+    }
+
+    // Moving pieces is an XOR so the same update both makes and unmakes a move
+    fn toggle_move(&mut self, mv: &crate::Move, captured: Bitboard) {
+        match mv {
+            Move::Pass => {}
             Move::Drop(sq) => {
-                let to_bb = crate::Bitboard::from_index(*sq);
-                let captured: Bitboard = to_bb.singles() & self.them();
+                let to_bb = crate::Bitboard::from_square(*sq);
 
                 self.pieces[self.turn as usize] ^= to_bb | captured;
                 self.pieces[!self.turn as usize] ^= captured;
 
-                self.halfmoves = 0;
-                self.fullmoves += (self.turn == Colour::White) as u32;
-                self.turn = !self.turn;
+                self.hash ^= ZOBRIST_PIECES[self.turn as usize][*sq as usize];
             }
             Move::Jump(fr, to) => {
-                let to_bb = crate::Bitboard::from_index(*to);
-                let from_bb = crate::Bitboard::from_index(*fr);
-                let captured: Bitboard = to_bb.singles() & self.them();
+                let to_bb = crate::Bitboard::from_square(*to);
+                let from_bb = crate::Bitboard::from_square(*fr);
 
                 self.pieces[self.turn as usize] ^= to_bb | from_bb | captured;
                 self.pieces[!self.turn as usize] ^= captured;
 
-                self.halfmoves += 1;
-                self.fullmoves += (self.turn == Colour::White) as u32;
-                self.turn = !self.turn;
+                self.hash ^= ZOBRIST_PIECES[self.turn as usize][*fr as usize]
+                    ^ ZOBRIST_PIECES[self.turn as usize][*to as usize];
             }
         }
+
+        for sq in captured {
+            self.hash ^= ZOBRIST_FLIP[sq as usize];
+        }
+        self.hash ^= ZOBRIST_TURN;
     }
 
     #[must_use]
     pub fn legal_moves(&self) -> Vec<Move> {
-        if self.is_gameover() {
-            return Vec::new();
-        }
-
-        self.pseudolegal_moves()
+        let mut list = MoveList::new();
+        self.legal_moves_into(&mut list);
+        list.as_slice().to_vec()
     }
 
     #[must_use]
     pub fn pseudolegal_moves(&self) -> Vec<Move> {
-        let mut vec = Vec::with_capacity(200);
+        let mut list = MoveList::new();
+        self.generate_moves_into(&mut list);
+        list.as_slice().to_vec()
+    }
+
+    /// Appends the legal moves to `list`, nothing is added once the game is over
+    pub fn legal_moves_into(&self, list: &mut MoveList) {
+        if !self.is_gameover() {
+            self.generate_moves_into(list);
+        }
+    }
+
+    /// Appends the pseudolegal moves to `list` without allocating
+    ///
+    /// `Move::Pass` is added only when the side to move has no other move.
+    pub fn generate_moves_into(&self, list: &mut MoveList) {
+        let start = list.len();
 
         // Single moves
         for sq in self.us().singles() & self.empty() {
-            vec.push(Move::Drop(sq));
+            list.push(Move::Drop(sq));
         }
 
         // Double moves
         for from in self.us() {
             for to in LUT_DOUBLES[from as usize] & self.empty() {
-                vec.push(Move::Jump(from, to));
+                list.push(Move::Jump(from, to));
             }
         }
 
-        vec
+        // Pass
+        if list.len() == start {
+            list.push(Move::Pass);
+        }
     }
 
     #[must_use]
     pub fn is_legal_move(&self, mv: &Move) -> bool {
-        match mv {
-            Move::Pass => self.can_pass(),
-            _ => self.pseudolegal_moves().iter().find(|&x| x == mv).is_some(),
+        let mut list = MoveList::new();
+        self.legal_moves_into(&mut list);
+        list.contains(mv)
+    }
+
+    /// Parses a move and checks that it is legal in this position
+    pub fn parse_move(&self, text: &str) -> Result<Move, MoveParseError> {
+        let mv: Move = text.parse()?;
+
+        if self.is_legal_move(&mv) {
+            Ok(mv)
+        } else {
+            Err(MoveParseError::Illegal(mv))
         }
     }
 
@@ -366,22 +568,82 @@ impl Board {
         self.halfmoves += 1;
         self.fullmoves += (self.turn == Colour::White) as u32;
         self.turn = !self.turn;
+        self.hash ^= ZOBRIST_TURN;
+    }
+
+    #[must_use]
+    pub fn transform(&self, t: Transform) -> Self {
+        Board::new(
+            [
+                self.black().transform(t),
+                self.white().transform(t),
+                self.blockers().transform(t),
+            ],
+            self.turn,
+            self.halfmoves,
+            self.fullmoves,
+        )
+    }
+
+    /// Swaps the black and white pieces and the side to move
+    #[must_use]
+    pub fn swap_colours(&self) -> Self {
+        Board::new(
+            [self.white(), self.black(), self.blockers()],
+            !self.turn,
+            self.halfmoves,
+            self.fullmoves,
+        )
+    }
+
+    /// The smallest of the 8 symmetric versions of this position, and the transform that produced it
+    ///
+    /// Positions are ordered by their black, white and then blocker bitboards.
+    #[must_use]
+    pub fn canonical(&self) -> (Self, Transform) {
+        Transform::ALL
+            .iter()
+            .map(|&t| (self.transform(t), t))
+            .min_by_key(|(pos, _)| (pos.black().0, pos.white().0, pos.blockers().0))
+            .unwrap()
     }
 
     #[must_use]
     pub fn is_valid(&self) -> bool {
-        if self.black() & self.white() != Bitboard(0) {
-            return false;
+        self.validate().is_ok()
+    }
+
+    /// Checks for overlapping pieces, pieces off the board and impossible counters
+    pub fn validate(&self) -> Result<(), BoardError> {
+        let overlap = (self.black() & self.white())
+            | (self.black() & self.blockers())
+            | (self.white() & self.blockers());
+        if let Some(sq) = overlap.into_iter().next() {
+            return Err(BoardError::Overlap(sq));
         }
 
-        if self.black() & self.blockers() != Bitboard(0) {
-            return false;
+        let outside =
+            Bitboard((self.black() | self.white() | self.blockers()).0 & !Bitboard::full().0);
+        if outside.is_occupied() {
+            return Err(BoardError::OutsideBoard(outside));
         }
 
-        if self.white() & self.blockers() != Bitboard(0) {
-            return false;
+        if self.halfmoves > 100 {
+            return Err(BoardError::InvalidHalfmoves(self.halfmoves));
         }
 
-        true
+        if self.fullmoves == 0 {
+            return Err(BoardError::InvalidFullmoves(self.fullmoves));
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Board {
+    type Err = FenError;
+
+    fn from_str(fen: &str) -> Result<Self, Self::Err> {
+        Board::try_from_fen(fen)
     }
 }
@@ -0,0 +1,119 @@
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::colour::Colour;
+use crate::squares::Square;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardError {
+    Overlap(Square),
+    OutsideBoard(Bitboard),
+    InvalidHalfmoves(u32),
+    InvalidFullmoves(u32),
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoardError::Overlap(sq) => write!(f, "more than one piece on {}", sq),
+            BoardError::OutsideBoard(bb) => write!(f, "bits set outside the board {:#x}", bb.0),
+            BoardError::InvalidHalfmoves(n) => write!(f, "halfmove counter {} is above 100", n),
+            BoardError::InvalidFullmoves(n) => write!(f, "fullmove counter {} is below 1", n),
+        }
+    }
+}
+
+impl Error for BoardError {}
+
+/// Builds a `Board` piece by piece, checking the result in `build()`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BoardBuilder {
+    pieces: [Bitboard; 3],
+    overlap: Bitboard,
+    turn: Colour,
+    halfmoves: u32,
+    fullmoves: u32,
+}
+
+impl BoardBuilder {
+    /// An empty board with black to move and the counters at "0 1"
+    #[must_use]
+    pub const fn new() -> Self {
+        BoardBuilder {
+            pieces: [Bitboard(0), Bitboard(0), Bitboard(0)],
+            overlap: Bitboard(0),
+            turn: Colour::Black,
+            halfmoves: 0,
+            fullmoves: 1,
+        }
+    }
+
+    #[must_use]
+    pub fn piece(self, colour: Colour, sq: Square) -> Self {
+        self.place(colour as usize, Bitboard::from_square(sq))
+    }
+
+    #[must_use]
+    pub fn black(self, sq: Square) -> Self {
+        self.piece(Colour::Black, sq)
+    }
+
+    #[must_use]
+    pub fn white(self, sq: Square) -> Self {
+        self.piece(Colour::White, sq)
+    }
+
+    #[must_use]
+    pub fn blocker(self, sq: Square) -> Self {
+        self.place(2, Bitboard::from_square(sq))
+    }
+
+    /// Adds whole bitboards of black, white and blocker pieces at once
+    #[must_use]
+    pub fn pieces(self, pieces: [Bitboard; 3]) -> Self {
+        self.place(0, pieces[0])
+            .place(1, pieces[1])
+            .place(2, pieces[2])
+    }
+
+    #[must_use]
+    pub fn turn(mut self, turn: Colour) -> Self {
+        self.turn = turn;
+        self
+    }
+
+    #[must_use]
+    pub fn halfmoves(mut self, halfmoves: u32) -> Self {
+        self.halfmoves = halfmoves;
+        self
+    }
+
+    #[must_use]
+    pub fn fullmoves(mut self, fullmoves: u32) -> Self {
+        self.fullmoves = fullmoves;
+        self
+    }
+
+    pub fn build(&self) -> Result<Board, BoardError> {
+        if let Some(sq) = self.overlap.into_iter().next() {
+            return Err(BoardError::Overlap(sq));
+        }
+
+        let pos = Board::new(self.pieces, self.turn, self.halfmoves, self.fullmoves);
+        pos.validate()?;
+        Ok(pos)
+    }
+
+    fn place(mut self, idx: usize, bb: Bitboard) -> Self {
+        self.overlap |= bb & (self.pieces[0] | self.pieces[1] | self.pieces[2]);
+        self.pieces[idx] |= bb;
+        self
+    }
+}
+
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -9,7 +9,6 @@ pub enum Colour {
 impl Not for Colour {
     type Output = Self;
 
-    #[must_use]
     fn not(self) -> Self::Output {
         match self {
             Colour::Black => Colour::White,
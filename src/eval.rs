@@ -0,0 +1,198 @@
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::lut::LUT_DOUBLES;
+use crate::squares::Square;
+use std::fmt;
+
+/// Static evaluation of a position
+pub trait Evaluator {
+    /// Score from the point of view of the side to move, positive is good for them
+    fn eval(&self, pos: &Board) -> i32;
+}
+
+/// Weights applied to each term of `HandcraftedEval`, every term is us minus them
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Weights {
+    pub material: i32,
+    pub mobility: i32,
+    pub frontier: i32,
+    pub edge: i32,
+    pub corner: i32,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Weights {
+            material: 100,
+            mobility: 4,
+            frontier: -8,
+            edge: 10,
+            corner: 25,
+        }
+    }
+}
+
+/// A raw count and the weight it is multiplied by
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Term {
+    pub value: i32,
+    pub weight: i32,
+}
+
+impl Term {
+    #[must_use]
+    pub const fn score(&self) -> i32 {
+        self.value * self.weight
+    }
+}
+
+/// Every term of a `HandcraftedEval` score
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub material: Term,
+    pub mobility: Term,
+    pub frontier: Term,
+    pub edge: Term,
+    pub corner: Term,
+}
+
+impl Explanation {
+    #[must_use]
+    pub const fn total(&self) -> i32 {
+        self.material.score()
+            + self.mobility.score()
+            + self.frontier.score()
+            + self.edge.score()
+            + self.corner.score()
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let terms = [
+            ("material", self.material),
+            ("mobility", self.mobility),
+            ("frontier", self.frontier),
+            ("edge", self.edge),
+            ("corner", self.corner),
+        ];
+
+        writeln!(f, "Term        Value  Weight   Score")?;
+        for (name, term) in terms {
+            writeln!(
+                f,
+                "{:<10} {:>6} {:>7} {:>7}",
+                name,
+                term.value,
+                term.weight,
+                term.score()
+            )?;
+        }
+        write!(f, "{:<10} {:>6} {:>7} {:>7}", "total", "", "", self.total())
+    }
+}
+
+const fn edge_masks() -> (Bitboard, Bitboard) {
+    let mut edges = 0;
+    let mut corners = 0;
+    let mut idx = 0;
+    while idx < 49 {
+        let sq = Square::ALL[idx];
+        let on_file = sq.file() == 0 || sq.file() == 6;
+        let on_rank = sq.rank() == 0 || sq.rank() == 6;
+        if on_file && on_rank {
+            corners |= 1 << idx;
+        } else if on_file || on_rank {
+            edges |= 1 << idx;
+        }
+        idx += 1;
+    }
+    (Bitboard(edges), Bitboard(corners))
+}
+
+/// Edge squares, not including the corners
+const EDGES: Bitboard = edge_masks().0;
+
+const CORNERS: Bitboard = edge_masks().1;
+
+/// Number of single and double moves available to `pieces`
+fn mobility(pieces: Bitboard, empty: Bitboard) -> i32 {
+    let singles = (pieces.singles() & empty).count();
+    let doubles: i32 = pieces
+        .into_iter()
+        .map(|sq| (LUT_DOUBLES[sq as usize] & empty).count())
+        .sum();
+    singles + doubles
+}
+
+/// Pieces next to at least one empty square, these are the ones that can be captured
+fn frontier(pieces: Bitboard, empty: Bitboard) -> i32 {
+    (pieces & empty.singles()).count()
+}
+
+/// Weighted sum of material, mobility, frontier pieces and edge and corner pieces
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct HandcraftedEval {
+    pub weights: Weights,
+}
+
+impl HandcraftedEval {
+    #[must_use]
+    pub const fn new(weights: Weights) -> Self {
+        HandcraftedEval { weights }
+    }
+
+    /// The same score as `eval`, split into its terms
+    #[must_use]
+    pub fn explain(&self, pos: &Board) -> Explanation {
+        let us = pos.us();
+        let them = pos.them();
+        let empty = pos.empty();
+
+        Explanation {
+            material: Term {
+                value: us.count() - them.count(),
+                weight: self.weights.material,
+            },
+            mobility: Term {
+                value: mobility(us, empty) - mobility(them, empty),
+                weight: self.weights.mobility,
+            },
+            frontier: Term {
+                value: frontier(us, empty) - frontier(them, empty),
+                weight: self.weights.frontier,
+            },
+            edge: Term {
+                value: (us & EDGES).count() - (them & EDGES).count(),
+                weight: self.weights.edge,
+            },
+            corner: Term {
+                value: (us & CORNERS).count() - (them & CORNERS).count(),
+                weight: self.weights.corner,
+            },
+        }
+    }
+}
+
+impl Evaluator for HandcraftedEval {
+    fn eval(&self, pos: &Board) -> i32 {
+        self.explain(pos).total()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks() {
+        assert_eq!(EDGES.count(), 20);
+        assert_eq!(CORNERS.count(), 4);
+        assert!(CORNERS.is_set(Square::A1));
+        assert!(CORNERS.is_set(Square::G7));
+        assert!(EDGES.is_set(Square::D1));
+        assert!(EDGES.is_set(Square::A4));
+        assert!(!EDGES.is_set(Square::A1));
+        assert!(!EDGES.is_set(Square::B2));
+    }
+}
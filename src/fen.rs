@@ -0,0 +1,39 @@
+use std::error::Error;
+use std::fmt;
+
+/// Reasons a FEN string can fail to parse.
+///
+/// Ranks are numbered 1 to 7 as they are on the board, so the first rank
+/// listed in a FEN is rank 7.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FenError {
+    WrongFieldCount(usize),
+    InvalidPiece { rank: u8, token: char },
+    RankTooLong { rank: u8 },
+    RankTooShort { rank: u8 },
+    TooManyRanks,
+    TooFewRanks(usize),
+    InvalidTurn(String),
+    InvalidHalfmoves(String),
+    InvalidFullmoves(String),
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FenError::WrongFieldCount(n) => write!(f, "expected 4 FEN fields, found {}", n),
+            FenError::InvalidPiece { rank, token } => {
+                write!(f, "unrecognised token '{}' on rank {}", token, rank)
+            }
+            FenError::RankTooLong { rank } => write!(f, "rank {} has more than 7 squares", rank),
+            FenError::RankTooShort { rank } => write!(f, "rank {} has fewer than 7 squares", rank),
+            FenError::TooManyRanks => write!(f, "more than 7 ranks"),
+            FenError::TooFewRanks(n) => write!(f, "expected 7 ranks, found {}", n),
+            FenError::InvalidTurn(s) => write!(f, "unrecognised side to move '{}'", s),
+            FenError::InvalidHalfmoves(s) => write!(f, "invalid halfmove counter '{}'", s),
+            FenError::InvalidFullmoves(s) => write!(f, "invalid fullmove counter '{}'", s),
+        }
+    }
+}
+
+impl Error for FenError {}
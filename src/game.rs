@@ -0,0 +1,250 @@
+use crate::board::*;
+use crate::fen::FenError;
+use crate::moves::{Move, MoveParseError};
+use crate::result::*;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameError {
+    Fen(FenError),
+    InvalidMove(MoveParseError),
+    IllegalMove(Move),
+    WrongFormat,
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::Fen(e) => write!(f, "{}", e),
+            GameError::InvalidMove(e) => write!(f, "{}", e),
+            GameError::IllegalMove(mv) => write!(f, "illegal move {}", mv),
+            GameError::WrongFormat => write!(f, "expected 'startpos' or 'fen <fen>'"),
+        }
+    }
+}
+
+impl Error for GameError {}
+
+impl From<FenError> for GameError {
+    fn from(e: FenError) -> Self {
+        GameError::Fen(e)
+    }
+}
+
+impl From<MoveParseError> for GameError {
+    fn from(e: MoveParseError) -> Self {
+        GameError::InvalidMove(e)
+    }
+}
+
+/// A start position and the moves played from it
+///
+/// Undone moves are kept until a different move is played so they can be redone.
+#[derive(Clone, Debug)]
+pub struct Game {
+    start: Board,
+    board: Board,
+    moves: Vec<Move>,
+    undos: Vec<Undo>,
+    keys: Vec<u64>,
+    redos: Vec<Move>,
+}
+
+/// Iterator over every position in a game, starting with the start position
+pub struct Positions<'a> {
+    board: Board,
+    moves: std::slice::Iter<'a, Move>,
+    done: bool,
+}
+
+impl Iterator for Positions<'_> {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Board> {
+        if self.done {
+            return None;
+        }
+
+        let pos = self.board;
+        match self.moves.next() {
+            Some(mv) => {
+                self.board.makemove(mv);
+            }
+            None => self.done = true,
+        }
+        Some(pos)
+    }
+}
+
+impl Game {
+    #[must_use]
+    pub fn new(start: Board) -> Self {
+        Game {
+            start,
+            board: start,
+            moves: Vec::new(),
+            undos: Vec::new(),
+            keys: vec![start.hash()],
+            redos: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn start(&self) -> &Board {
+        &self.start
+    }
+
+    #[must_use]
+    pub const fn board(&self) -> &Board {
+        &self.board
+    }
+
+    #[must_use]
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Position keys of every position in the game, starting with the start position
+    #[must_use]
+    pub fn keys(&self) -> &[u64] {
+        &self.keys
+    }
+
+    #[must_use]
+    pub fn positions(&self) -> Positions<'_> {
+        Positions {
+            board: self.start,
+            moves: self.moves.iter(),
+            done: false,
+        }
+    }
+
+    /// Plays a move, refusing it once the game is over
+    ///
+    /// A threefold repetition ends the game, so no move is accepted after one even if
+    /// the board would allow it. Use `replay` to follow moves that were already played.
+    pub fn play(&mut self, mv: Move) -> Result<(), GameError> {
+        if self.is_repetition() {
+            return Err(GameError::IllegalMove(mv));
+        }
+        self.replay(mv)
+    }
+
+    /// Plays a move given in text form, such as "a1", "a1c3" or "0000"
+    pub fn play_str(&mut self, text: &str) -> Result<(), GameError> {
+        let mv = self.board.parse_move(text)?;
+        self.play(mv)
+    }
+
+    /// Plays a move that only has to be legal on the board
+    ///
+    /// Repetitions are not adjudicated, so a game received from elsewhere can be
+    /// followed past a threefold repetition.
+    pub fn replay(&mut self, mv: Move) -> Result<(), GameError> {
+        if self.board.is_gameover() || !self.board.is_legal_move(&mv) {
+            return Err(GameError::IllegalMove(mv));
+        }
+
+        self.push(mv);
+        self.redos.clear();
+
+        Ok(())
+    }
+
+    /// Replays a move given in text form, such as "a1", "a1c3" or "0000"
+    pub fn replay_str(&mut self, text: &str) -> Result<(), GameError> {
+        let mv = self.board.parse_move(text)?;
+        self.replay(mv)
+    }
+
+    pub fn undo(&mut self) -> Option<Move> {
+        let mv = self.moves.pop()?;
+        let undo = self.undos.pop()?;
+        self.keys.pop();
+        self.board.unmakemove(&mv, &undo);
+        self.redos.push(mv);
+        Some(mv)
+    }
+
+    pub fn redo(&mut self) -> Option<Move> {
+        let mv = self.redos.pop()?;
+        self.push(mv);
+        Some(mv)
+    }
+
+    /// How many times the current position has occurred, including now
+    ///
+    /// Only positions since the last single move are searched, because single moves
+    /// add a piece to the board and so can never be repeated.
+    #[must_use]
+    pub fn repetitions(&self) -> usize {
+        let current = self.board.hash();
+        let window = (self.board.halfmoves() as usize).min(self.keys.len() - 1);
+
+        self.keys
+            .iter()
+            .rev()
+            .take(window + 1)
+            .filter(|&&key| key == current)
+            .count()
+    }
+
+    #[must_use]
+    pub fn is_repetition(&self) -> bool {
+        self.repetitions() >= 3
+    }
+
+    #[must_use]
+    pub fn is_gameover(&self) -> bool {
+        self.board.is_gameover() || self.is_repetition()
+    }
+
+    #[must_use]
+    pub fn result(&self) -> Option<GameResult> {
+        match self.board.result() {
+            Some(result) => Some(result),
+            None if self.is_repetition() => {
+                Some(GameResult::new(Outcome::Draw, Termination::Repetition))
+            }
+            None => None,
+        }
+    }
+
+    fn push(&mut self, mv: Move) {
+        let undo = self.board.makemove(&mv);
+        self.moves.push(mv);
+        self.undos.push(undo);
+        self.keys.push(self.board.hash());
+    }
+}
+
+impl FromStr for Game {
+    type Err = GameError;
+
+    /// Parses "startpos moves a1 b2 ..." or "fen <fen> moves a1 b2 ..."
+    ///
+    /// The moves are replayed, so they only have to be legal on the board.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (position, moves) = match s.split_once(" moves") {
+            Some((position, moves)) => (position.trim(), moves),
+            None => (s.trim(), ""),
+        };
+
+        let board = if position == "startpos" {
+            Board::try_from_fen("startpos")?
+        } else if let Some(fen) = position.strip_prefix("fen ") {
+            Board::try_from_fen(fen)?
+        } else {
+            return Err(GameError::WrongFormat);
+        };
+
+        let mut game = Game::new(board);
+        for text in moves.split_whitespace() {
+            game.replay_str(text)?;
+        }
+
+        Ok(game)
+    }
+}
@@ -1,20 +1,68 @@
 mod board;
 pub use crate::board::*;
 
+mod tt;
+pub use crate::tt::*;
+
+mod builder;
+pub use crate::builder::*;
+
 mod bitboard;
 pub use crate::bitboard::*;
 
+mod fen;
+pub use crate::fen::*;
+
 mod colour;
 pub use crate::colour::*;
 
 mod squares;
 pub use crate::squares::*;
 
+mod game;
+pub use crate::game::*;
+
 mod moves;
 pub use crate::moves::*;
 
+mod movelist;
+pub use crate::movelist::*;
+
+mod result;
+pub use crate::result::*;
+
+mod symmetry;
+pub use crate::symmetry::*;
+
 mod perft;
 pub use crate::perft::*;
 
+mod suite;
+pub use crate::suite::*;
+
+mod pgn;
+pub use crate::pgn::*;
+
 mod lut;
 pub use crate::lut::*;
+
+mod zobrist;
+pub use crate::zobrist::*;
+
+mod eval;
+pub use crate::eval::*;
+
+mod timeman;
+pub use crate::timeman::*;
+
+mod search;
+pub use crate::search::*;
+
+mod rng;
+pub use crate::rng::*;
+
+mod mcts;
+pub use crate::mcts::*;
+
+mod matches;
+pub use crate::matches::*;
@@ -1,53 +1,53 @@
-use crate::Bitboard;
+use crate::{Bitboard, Square};
 
 pub const LUT_DOUBLES: [Bitboard; 49] = [
-    Bitboard::from_index(0).doubles(),
-    Bitboard::from_index(1).doubles(),
-    Bitboard::from_index(2).doubles(),
-    Bitboard::from_index(3).doubles(),
-    Bitboard::from_index(4).doubles(),
-    Bitboard::from_index(5).doubles(),
-    Bitboard::from_index(6).doubles(),
-    Bitboard::from_index(7).doubles(),
-    Bitboard::from_index(8).doubles(),
-    Bitboard::from_index(9).doubles(),
-    Bitboard::from_index(10).doubles(),
-    Bitboard::from_index(11).doubles(),
-    Bitboard::from_index(12).doubles(),
-    Bitboard::from_index(13).doubles(),
-    Bitboard::from_index(14).doubles(),
-    Bitboard::from_index(15).doubles(),
-    Bitboard::from_index(16).doubles(),
-    Bitboard::from_index(17).doubles(),
-    Bitboard::from_index(18).doubles(),
-    Bitboard::from_index(19).doubles(),
-    Bitboard::from_index(20).doubles(),
-    Bitboard::from_index(21).doubles(),
-    Bitboard::from_index(22).doubles(),
-    Bitboard::from_index(23).doubles(),
-    Bitboard::from_index(24).doubles(),
-    Bitboard::from_index(25).doubles(),
-    Bitboard::from_index(26).doubles(),
-    Bitboard::from_index(27).doubles(),
-    Bitboard::from_index(28).doubles(),
-    Bitboard::from_index(29).doubles(),
-    Bitboard::from_index(30).doubles(),
-    Bitboard::from_index(31).doubles(),
-    Bitboard::from_index(32).doubles(),
-    Bitboard::from_index(33).doubles(),
-    Bitboard::from_index(34).doubles(),
-    Bitboard::from_index(35).doubles(),
-    Bitboard::from_index(36).doubles(),
-    Bitboard::from_index(37).doubles(),
-    Bitboard::from_index(38).doubles(),
-    Bitboard::from_index(39).doubles(),
-    Bitboard::from_index(40).doubles(),
-    Bitboard::from_index(41).doubles(),
-    Bitboard::from_index(42).doubles(),
-    Bitboard::from_index(43).doubles(),
-    Bitboard::from_index(44).doubles(),
-    Bitboard::from_index(45).doubles(),
-    Bitboard::from_index(46).doubles(),
-    Bitboard::from_index(47).doubles(),
-    Bitboard::from_index(48).doubles(),
+    Bitboard::from_square(Square::A1).doubles(),
+    Bitboard::from_square(Square::B1).doubles(),
+    Bitboard::from_square(Square::C1).doubles(),
+    Bitboard::from_square(Square::D1).doubles(),
+    Bitboard::from_square(Square::E1).doubles(),
+    Bitboard::from_square(Square::F1).doubles(),
+    Bitboard::from_square(Square::G1).doubles(),
+    Bitboard::from_square(Square::A2).doubles(),
+    Bitboard::from_square(Square::B2).doubles(),
+    Bitboard::from_square(Square::C2).doubles(),
+    Bitboard::from_square(Square::D2).doubles(),
+    Bitboard::from_square(Square::E2).doubles(),
+    Bitboard::from_square(Square::F2).doubles(),
+    Bitboard::from_square(Square::G2).doubles(),
+    Bitboard::from_square(Square::A3).doubles(),
+    Bitboard::from_square(Square::B3).doubles(),
+    Bitboard::from_square(Square::C3).doubles(),
+    Bitboard::from_square(Square::D3).doubles(),
+    Bitboard::from_square(Square::E3).doubles(),
+    Bitboard::from_square(Square::F3).doubles(),
+    Bitboard::from_square(Square::G3).doubles(),
+    Bitboard::from_square(Square::A4).doubles(),
+    Bitboard::from_square(Square::B4).doubles(),
+    Bitboard::from_square(Square::C4).doubles(),
+    Bitboard::from_square(Square::D4).doubles(),
+    Bitboard::from_square(Square::E4).doubles(),
+    Bitboard::from_square(Square::F4).doubles(),
+    Bitboard::from_square(Square::G4).doubles(),
+    Bitboard::from_square(Square::A5).doubles(),
+    Bitboard::from_square(Square::B5).doubles(),
+    Bitboard::from_square(Square::C5).doubles(),
+    Bitboard::from_square(Square::D5).doubles(),
+    Bitboard::from_square(Square::E5).doubles(),
+    Bitboard::from_square(Square::F5).doubles(),
+    Bitboard::from_square(Square::G5).doubles(),
+    Bitboard::from_square(Square::A6).doubles(),
+    Bitboard::from_square(Square::B6).doubles(),
+    Bitboard::from_square(Square::C6).doubles(),
+    Bitboard::from_square(Square::D6).doubles(),
+    Bitboard::from_square(Square::E6).doubles(),
+    Bitboard::from_square(Square::F6).doubles(),
+    Bitboard::from_square(Square::G6).doubles(),
+    Bitboard::from_square(Square::A7).doubles(),
+    Bitboard::from_square(Square::B7).doubles(),
+    Bitboard::from_square(Square::C7).doubles(),
+    Bitboard::from_square(Square::D7).doubles(),
+    Bitboard::from_square(Square::E7).doubles(),
+    Bitboard::from_square(Square::F7).doubles(),
+    Bitboard::from_square(Square::G7).doubles(),
 ];
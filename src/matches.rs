@@ -0,0 +1,425 @@
+use crate::board::Board;
+use crate::colour::Colour;
+use crate::game::Game;
+use crate::moves::Move;
+use crate::pgn::PgnGame;
+use crate::result::Outcome;
+use crate::suite::{parse_suite, SuiteLineError};
+use crate::timeman::Clocks;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How to start a UAI engine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineConfig {
+    /// Used for the White and Black tags
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+    /// Sent as "setoption name <name> value <value>" once the engine has started
+    pub options: Vec<(String, String)>,
+}
+
+impl EngineConfig {
+    #[must_use]
+    pub fn new(name: &str, command: &str) -> Self {
+        EngineConfig {
+            name: name.to_string(),
+            command: command.to_string(),
+            args: Vec::new(),
+            options: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchConfig {
+    pub engines: [EngineConfig; 2],
+    /// Each opening is played twice, with the engines swapping colours
+    pub openings: Vec<Board>,
+    pub time: Duration,
+    pub increment: Duration,
+    /// Games played at once, each with its own pair of engine processes
+    pub concurrency: usize,
+    /// How long to wait for "uaiok" and "readyok", and for a move after the clock runs out
+    /// before the engine is considered hung and killed
+    pub timeout: Duration,
+}
+
+impl MatchConfig {
+    /// One game pair from the start position at 10+0.1
+    #[must_use]
+    pub fn new(engines: [EngineConfig; 2]) -> Self {
+        MatchConfig {
+            engines,
+            openings: vec![Board::from_fen("startpos")],
+            time: Duration::from_secs(10),
+            increment: Duration::from_millis(100),
+            concurrency: 1,
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Reads an opening file, one FEN per line in the same format as suite files
+pub fn parse_openings(text: &str) -> Result<Vec<Board>, SuiteLineError> {
+    Ok(parse_suite(text)?
+        .into_iter()
+        .map(|entry| entry.board)
+        .collect())
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MatchReport {
+    /// Every game in the order they were scheduled, pairs of games share an opening
+    pub games: Vec<PgnGame>,
+    /// Results for the first engine
+    pub wins: usize,
+    pub losses: usize,
+    pub draws: usize,
+}
+
+impl MatchReport {
+    /// Points per game for the first engine, counting a draw as half a point
+    #[must_use]
+    pub fn score(&self) -> f64 {
+        (self.wins as f64 + self.draws as f64 / 2.0) / self.games.len().max(1) as f64
+    }
+}
+
+/// Plays every opening twice with colours swapped, writing each game to `pgn` as it finishes
+///
+/// Engines that crash, hang, run out of time or play an illegal move lose that game.
+/// Engines that crashed or hung are restarted for the next one.
+pub fn run_match<W: Write + Send>(config: &MatchConfig, pgn: W) -> io::Result<MatchReport> {
+    let total = config.openings.len() * 2;
+    let next = AtomicUsize::new(0);
+    let pgn = Mutex::new(pgn);
+
+    let mut results =
+        std::thread::scope(|s| -> io::Result<Vec<(usize, PgnGame, Option<usize>)>> {
+            let workers: Vec<_> = (0..config.concurrency.clamp(1, total.max(1)))
+                .map(|_| {
+                    s.spawn(|| -> io::Result<Vec<(usize, PgnGame, Option<usize>)>> {
+                        let mut engines = [None, None];
+                        let mut results = Vec::new();
+
+                        loop {
+                            let idx = next.fetch_add(1, Ordering::Relaxed);
+                            if idx >= total {
+                                break;
+                            }
+
+                            let (game, winner) = play_game(config, &mut engines, idx);
+                            write!(pgn.lock().expect("pgn lock poisoned"), "{}", game)?;
+                            results.push((idx, game, winner));
+                        }
+
+                        Ok(results)
+                    })
+                })
+                .collect();
+
+            let mut results = Vec::new();
+            for worker in workers {
+                results.extend(worker.join().expect("match worker panicked")?);
+            }
+            Ok(results)
+        })?;
+
+    results.sort_by_key(|(idx, _, _)| *idx);
+    pgn.lock().expect("pgn lock poisoned").flush()?;
+
+    let mut report = MatchReport::default();
+    for (_, game, winner) in results {
+        match winner {
+            Some(0) => report.wins += 1,
+            Some(_) => report.losses += 1,
+            None => report.draws += 1,
+        }
+        report.games.push(game);
+    }
+
+    Ok(report)
+}
+
+/// Why an engine lost a game without it being played out
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum EngineError {
+    Spawn(String),
+    Crashed,
+    /// No reply before the deadline, the engine is killed
+    Timeout,
+    /// A move that came after the clock ran out
+    OutOfTime,
+    IllegalMove(String),
+}
+
+impl EngineError {
+    /// The PGN Termination tag
+    const fn termination(&self) -> &'static str {
+        match self {
+            EngineError::Spawn(_) | EngineError::Crashed => "abandoned",
+            EngineError::Timeout | EngineError::OutOfTime => "time forfeit",
+            EngineError::IllegalMove(_) => "rules infraction",
+        }
+    }
+
+    /// Whether the engine needs restarting before it can play again
+    const fn is_fatal(&self) -> bool {
+        matches!(
+            self,
+            EngineError::Spawn(_) | EngineError::Crashed | EngineError::Timeout
+        )
+    }
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EngineError::Spawn(e) => write!(f, "could not be started: {}", e),
+            EngineError::Crashed => write!(f, "crashed"),
+            EngineError::Timeout => write!(f, "stopped responding"),
+            EngineError::OutOfTime => write!(f, "ran out of time"),
+            EngineError::IllegalMove(mv) => write!(f, "played illegal move '{}'", mv),
+        }
+    }
+}
+
+/// A running engine, its output is read on another thread so waits can time out
+struct UaiProcess {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+}
+
+impl UaiProcess {
+    fn start(config: &EngineConfig, timeout: Duration) -> Result<Self, EngineError> {
+        let mut child = Command::new(&config.command)
+            .args(&config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| EngineError::Spawn(e.to_string()))?;
+
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let (sender, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut engine = UaiProcess {
+            child,
+            stdin,
+            lines,
+        };
+        engine.send("uai")?;
+        engine.wait_for("uaiok", Instant::now() + timeout)?;
+        for (name, value) in &config.options {
+            engine.send(&format!("setoption name {} value {}", name, value))?;
+        }
+        engine.ready(timeout)?;
+
+        Ok(engine)
+    }
+
+    fn send(&mut self, command: &str) -> Result<(), EngineError> {
+        writeln!(self.stdin, "{}", command)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|_| EngineError::Crashed)
+    }
+
+    /// Skips output until a line starting with the word `first`, and returns that line
+    fn wait_for(&mut self, first: &str, deadline: Instant) -> Result<String, EngineError> {
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.lines.recv_timeout(remaining) {
+                Ok(line) if line.split_whitespace().next() == Some(first) => return Ok(line),
+                Ok(_) => {}
+                Err(RecvTimeoutError::Timeout) => return Err(EngineError::Timeout),
+                Err(RecvTimeoutError::Disconnected) => return Err(EngineError::Crashed),
+            }
+        }
+    }
+
+    fn ready(&mut self, timeout: Duration) -> Result<(), EngineError> {
+        self.send("isready")?;
+        self.wait_for("readyok", Instant::now() + timeout)?;
+        Ok(())
+    }
+
+    /// Searches the current position of `game` and returns the move and how long it took
+    fn go(
+        &mut self,
+        game: &Game,
+        clocks: &Clocks,
+        timeout: Duration,
+    ) -> Result<(Move, Duration), EngineError> {
+        let mut position = format!("position fen {}", game.start().get_fen());
+        if !game.moves().is_empty() {
+            let moves: Vec<String> = game.moves().iter().map(|mv| mv.to_string()).collect();
+            position = format!("{} moves {}", position, moves.join(" "));
+        }
+        self.send(&position)?;
+
+        let remaining = match game.board().turn() {
+            Colour::Black => clocks.btime,
+            Colour::White => clocks.wtime,
+        };
+        let start = Instant::now();
+        self.send(&format!(
+            "go btime {} wtime {} binc {} winc {}",
+            clocks.btime.as_millis(),
+            clocks.wtime.as_millis(),
+            clocks.binc.as_millis(),
+            clocks.winc.as_millis()
+        ))?;
+
+        let line = self.wait_for("bestmove", start + remaining + timeout)?;
+        let elapsed = start.elapsed();
+        let text = line.split_whitespace().nth(1).unwrap_or("");
+
+        let mv = game
+            .board()
+            .parse_move(text)
+            .map_err(|_| EngineError::IllegalMove(text.to_string()))?;
+        if elapsed > remaining {
+            return Err(EngineError::OutOfTime);
+        }
+
+        Ok((mv, elapsed))
+    }
+}
+
+impl Drop for UaiProcess {
+    /// Asks the engine to quit, and kills it if it has not after a short wait
+    fn drop(&mut self) {
+        if self.send("quit").is_ok() {
+            for _ in 0..50 {
+                if let Ok(Some(_)) = self.child.try_wait() {
+                    return;
+                }
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Starts the engine if it is not running, and gets it ready for a new game
+fn prepare(
+    engine: &mut Option<UaiProcess>,
+    config: &EngineConfig,
+    timeout: Duration,
+) -> Result<(), EngineError> {
+    let process = match engine {
+        Some(process) => process,
+        None => engine.insert(UaiProcess::start(config, timeout)?),
+    };
+    process.send("uainewgame")?;
+    process.ready(timeout)
+}
+
+/// Plays game `idx` of the match and returns it with the index of the winning engine
+fn play_game(
+    config: &MatchConfig,
+    engines: &mut [Option<UaiProcess>; 2],
+    idx: usize,
+) -> (PgnGame, Option<usize>) {
+    let opening = config.openings[idx / 2];
+    let black = idx % 2;
+    let engine_for = |colour: Colour| match colour {
+        Colour::Black => black,
+        Colour::White => 1 - black,
+    };
+
+    let mut game = Game::new(opening);
+    let mut clocks = Clocks {
+        btime: config.time,
+        wtime: config.time,
+        binc: config.increment,
+        winc: config.increment,
+        movestogo: None,
+    };
+    let mut forfeit = None;
+
+    for colour in [Colour::Black, Colour::White] {
+        let id = engine_for(colour);
+        if let Err(e) = prepare(&mut engines[id], &config.engines[id], config.timeout) {
+            forfeit = Some((colour, e));
+            break;
+        }
+    }
+
+    while forfeit.is_none() && !game.is_gameover() {
+        let turn = game.board().turn();
+        let id = engine_for(turn);
+        let engine = engines[id]
+            .as_mut()
+            .expect("engines are started before the game");
+
+        match engine.go(&game, &clocks, config.timeout) {
+            Ok((mv, elapsed)) => {
+                let (time, inc) = match turn {
+                    Colour::Black => (&mut clocks.btime, clocks.binc),
+                    Colour::White => (&mut clocks.wtime, clocks.winc),
+                };
+                *time = *time - elapsed + inc;
+                game.play(mv).expect("the move was checked by parse_move");
+            }
+            Err(e) => forfeit = Some((turn, e)),
+        }
+    }
+
+    let mut pgn = PgnGame::from_game(&game);
+    pgn.set_tag("Round", &format!("{}.{}", idx / 2 + 1, idx % 2 + 1));
+    pgn.set_tag("White", &config.engines[1 - black].name);
+    pgn.set_tag("Black", &config.engines[black].name);
+    pgn.set_tag(
+        "TimeControl",
+        &format!(
+            "{}+{}",
+            config.time.as_secs_f64(),
+            config.increment.as_secs_f64()
+        ),
+    );
+
+    match forfeit {
+        Some((colour, e)) => {
+            let id = engine_for(colour);
+            pgn.outcome = Some(match colour {
+                Colour::Black => Outcome::WhiteWin,
+                Colour::White => Outcome::BlackWin,
+            });
+            pgn.set_tag("Termination", e.termination());
+            pgn.comments.push((
+                pgn.moves.len(),
+                format!("{} {}", config.engines[id].name, e),
+            ));
+            if e.is_fatal() {
+                engines[id] = None;
+            }
+        }
+        None => pgn.set_tag("Termination", "normal"),
+    }
+
+    let winner = match pgn.outcome {
+        Some(Outcome::BlackWin) => Some(black),
+        Some(Outcome::WhiteWin) => Some(1 - black),
+        _ => None,
+    };
+
+    (pgn, winner)
+}
@@ -0,0 +1,339 @@
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::colour::Colour;
+use crate::movelist::MoveList;
+use crate::moves::Move;
+use crate::rng::Rng;
+use crate::search::SearchLimits;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+/// How moves are picked during playouts
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Playout {
+    /// Every move is equally likely
+    Random,
+    /// The move gaining the most pieces out of a few random ones
+    Biased,
+}
+
+#[derive(Debug, Clone)]
+pub struct MctsConfig {
+    /// The UCT exploration constant, higher values try more moves
+    pub exploration: f64,
+    pub playout: Playout,
+    pub seed: u64,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        MctsConfig {
+            exploration: std::f64::consts::SQRT_2,
+            playout: Playout::Biased,
+            seed: 0,
+        }
+    }
+}
+
+/// Visits and win rate of a root move, the win rate is for the side to move at the root
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MoveStats {
+    pub mv: Move,
+    pub visits: u32,
+    pub win_rate: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MctsResult {
+    /// The most visited root move
+    pub best_move: Option<Move>,
+    /// Root moves, most visited first
+    pub moves: Vec<MoveStats>,
+    /// Playouts in this search
+    pub nodes: u64,
+    pub time: Duration,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    pos: Board,
+    mv: Option<Move>,
+    children: Vec<usize>,
+    untried: Vec<Move>,
+    visits: u32,
+    /// Points for the side that played `mv`, 1 for a win and 0.5 for a draw
+    wins: f64,
+}
+
+impl Node {
+    fn new(pos: Board, mv: Option<Move>) -> Self {
+        let mut moves = MoveList::new();
+        pos.legal_moves_into(&mut moves);
+
+        Node {
+            pos,
+            mv,
+            children: Vec::new(),
+            untried: moves.as_slice().to_vec(),
+            visits: 0,
+            wins: 0.0,
+        }
+    }
+}
+
+/// Monte Carlo tree search with UCT selection
+///
+/// The tree is kept between searches, `play` moves the root down to the new position.
+#[derive(Debug, Clone)]
+pub struct Mcts {
+    config: MctsConfig,
+    rng: Rng,
+    nodes: Vec<Node>,
+}
+
+impl Mcts {
+    #[must_use]
+    pub fn new(pos: Board, config: MctsConfig) -> Self {
+        Mcts {
+            rng: Rng::new(config.seed),
+            config,
+            nodes: vec![Node::new(pos, None)],
+        }
+    }
+
+    #[must_use]
+    pub fn board(&self) -> &Board {
+        &self.nodes[0].pos
+    }
+
+    /// Number of nodes in the tree
+    #[must_use]
+    pub fn tree_size(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Playouts through the current root, including ones from earlier searches
+    #[must_use]
+    pub fn visits(&self) -> u32 {
+        self.nodes[0].visits
+    }
+
+    /// Moves the root to the position after `mv`, keeping that part of the tree
+    pub fn play(&mut self, mv: &Move) {
+        let child = self.nodes[0]
+            .children
+            .iter()
+            .copied()
+            .find(|&idx| self.nodes[idx].mv == Some(*mv));
+
+        match child {
+            Some(idx) => self.reroot(idx),
+            None => {
+                let pos = self.nodes[0].pos.after_move(mv);
+                self.nodes = vec![Node::new(pos, None)];
+            }
+        }
+    }
+
+    /// Moves the root to `pos`, keeping the tree if it is at most two moves ahead
+    pub fn set_position(&mut self, pos: &Board) {
+        let root = &self.nodes[0];
+        if root.pos == *pos {
+            return;
+        }
+
+        for &child in &root.children {
+            for &grandchild in &self.nodes[child].children {
+                if self.nodes[grandchild].pos == *pos {
+                    self.reroot(grandchild);
+                    return;
+                }
+            }
+        }
+
+        self.nodes = vec![Node::new(*pos, None)];
+    }
+
+    /// Runs playouts until the node or time limit, `depth` and `searchmoves` are ignored
+    pub fn search(&mut self, limits: &SearchLimits) -> MctsResult {
+        let start = Instant::now();
+        let mut playouts = 0;
+
+        loop {
+            // Always do at least one playout so there is a move to report
+            if playouts > 0 && playouts % 64 == 0 {
+                let stopped = limits.stop.load(Ordering::Relaxed);
+                let over_time = match limits.movetime {
+                    Some(movetime) => !limits.infinite && start.elapsed() >= movetime,
+                    None => false,
+                };
+                if stopped || over_time {
+                    break;
+                }
+            }
+
+            if let Some(nodes) = limits.nodes {
+                if !limits.infinite && playouts >= nodes {
+                    break;
+                }
+            }
+
+            if self.nodes[0].untried.is_empty() && self.nodes[0].children.is_empty() {
+                break;
+            }
+
+            self.iteration();
+            playouts += 1;
+        }
+
+        let moves = self.root_moves();
+        MctsResult {
+            best_move: moves.first().map(|stats| stats.mv),
+            moves,
+            nodes: playouts,
+            time: start.elapsed(),
+        }
+    }
+
+    /// Root moves that have been tried, most visited first
+    #[must_use]
+    pub fn root_moves(&self) -> Vec<MoveStats> {
+        let mut moves: Vec<MoveStats> = self.nodes[0]
+            .children
+            .iter()
+            .map(|&idx| {
+                let node = &self.nodes[idx];
+                MoveStats {
+                    mv: node.mv.expect("child nodes have a move"),
+                    visits: node.visits,
+                    win_rate: node.wins / node.visits.max(1) as f64,
+                }
+            })
+            .collect();
+
+        moves.sort_by_key(|stats| std::cmp::Reverse(stats.visits));
+        moves
+    }
+
+    fn iteration(&mut self) {
+        let mut path = vec![0];
+        let mut idx = 0;
+
+        // Selection
+        while self.nodes[idx].untried.is_empty() && !self.nodes[idx].children.is_empty() {
+            idx = self.select(idx);
+            path.push(idx);
+        }
+
+        // Expansion
+        if !self.nodes[idx].untried.is_empty() {
+            let untried = &mut self.nodes[idx].untried;
+            let mv = untried.swap_remove(self.rng.below(untried.len()));
+            let pos = self.nodes[idx].pos.after_move(&mv);
+
+            self.nodes.push(Node::new(pos, Some(mv)));
+            let child = self.nodes.len() - 1;
+            self.nodes[idx].children.push(child);
+            idx = child;
+            path.push(idx);
+        }
+
+        // Simulation
+        let winner = self.playout(self.nodes[idx].pos);
+
+        // Backpropagation
+        for idx in path {
+            let node = &mut self.nodes[idx];
+            node.visits += 1;
+            node.wins += match winner {
+                Some(colour) if colour != node.pos.turn() => 1.0,
+                Some(_) => 0.0,
+                None => 0.5,
+            };
+        }
+    }
+
+    fn select(&self, parent: usize) -> usize {
+        let log_visits = (self.nodes[parent].visits.max(1) as f64).ln();
+        let mut best = self.nodes[parent].children[0];
+        let mut best_score = f64::NEG_INFINITY;
+
+        for &idx in &self.nodes[parent].children {
+            let node = &self.nodes[idx];
+            let visits = node.visits.max(1) as f64;
+            let score = node.wins / visits + self.config.exploration * (log_visits / visits).sqrt();
+
+            if score > best_score {
+                best_score = score;
+                best = idx;
+            }
+        }
+
+        best
+    }
+
+    /// Plays until the game ends and returns the winner
+    fn playout(&mut self, mut pos: Board) -> Option<Colour> {
+        let mut moves = MoveList::new();
+
+        while !pos.is_gameover() {
+            moves.clear();
+            pos.generate_moves_into(&mut moves);
+
+            let mv = match self.config.playout {
+                Playout::Random => moves[self.rng.below(moves.len())],
+                Playout::Biased => {
+                    let mut best = moves[self.rng.below(moves.len())];
+                    let mut best_gain = gain(&pos, &best);
+                    for _ in 0..3 {
+                        let mv = moves[self.rng.below(moves.len())];
+                        let gain = gain(&pos, &mv);
+                        if gain > best_gain {
+                            best = mv;
+                            best_gain = gain;
+                        }
+                    }
+                    best
+                }
+            };
+
+            pos.makemove(&mv);
+        }
+
+        pos.result().and_then(|result| result.winner())
+    }
+
+    /// Copies the subtree under `idx` into a new tree with it as the root
+    fn reroot(&mut self, idx: usize) {
+        let mut old: Vec<Option<Node>> = std::mem::take(&mut self.nodes)
+            .into_iter()
+            .map(Some)
+            .collect();
+        let mut stack: Vec<(usize, Option<usize>)> = vec![(idx, None)];
+
+        while let Some((old_idx, parent)) = stack.pop() {
+            let mut node = old[old_idx].take().expect("nodes are only in one subtree");
+            let children = std::mem::take(&mut node.children);
+            self.nodes.push(node);
+            let new_idx = self.nodes.len() - 1;
+
+            if let Some(parent) = parent {
+                self.nodes[parent].children.push(new_idx);
+            }
+            for child in children.into_iter().rev() {
+                stack.push((child, Some(new_idx)));
+            }
+        }
+
+        self.nodes[0].mv = None;
+    }
+}
+
+/// Pieces gained by a move, singles count one extra for the new piece
+fn gain(pos: &Board, mv: &Move) -> i32 {
+    match mv {
+        Move::Pass => 0,
+        Move::Drop(sq) => 1 + (Bitboard::from_square(*sq).singles() & pos.them()).count(),
+        Move::Jump(_, to) => (Bitboard::from_square(*to).singles() & pos.them()).count(),
+    }
+}
@@ -0,0 +1,110 @@
+use crate::moves::Move;
+use std::ops::Index;
+
+/// More than the number of moves available in any Ataxx position
+pub const MAX_MOVES: usize = 256;
+
+/// Fixed capacity list of moves that lives on the stack
+#[derive(Copy, Clone, Debug)]
+pub struct MoveList {
+    moves: [Move; MAX_MOVES],
+    len: usize,
+}
+
+impl MoveList {
+    #[must_use]
+    pub const fn new() -> Self {
+        MoveList {
+            moves: [Move::Pass; MAX_MOVES],
+            len: 0,
+        }
+    }
+
+    pub fn push(&mut self, mv: Move) {
+        self.moves[self.len] = mv;
+        self.len += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[must_use]
+    pub fn as_slice(&self) -> &[Move] {
+        &self.moves[..self.len]
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Move> {
+        self.as_slice().iter()
+    }
+
+    #[must_use]
+    pub fn contains(&self, mv: &Move) -> bool {
+        self.as_slice().contains(mv)
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Index<usize> for MoveList {
+    type Output = Move;
+
+    fn index(&self, idx: usize) -> &Move {
+        &self.as_slice()[idx]
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Move, MoveList, Square};
+
+    #[test]
+    fn push() {
+        let mut list = MoveList::new();
+        assert!(list.is_empty());
+
+        list.push(Move::Drop(Square::D1));
+        list.push(Move::Jump(Square::A1, Square::C1));
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0], Move::Drop(Square::D1));
+        assert_eq!(list[1], Move::Jump(Square::A1, Square::C1));
+        assert!(list.contains(&Move::Jump(Square::A1, Square::C1)));
+        assert!(!list.contains(&Move::Pass));
+        assert_eq!(list.iter().count(), 2);
+
+        list.clear();
+        assert!(list.is_empty());
+        assert_eq!(list.iter().next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_past_len() {
+        let mut list = MoveList::new();
+        list.push(Move::Drop(Square::D1));
+        let _ = list[1];
+    }
+}
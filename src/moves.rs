@@ -1,40 +1,33 @@
-use std::char;
+use crate::squares::Square;
+use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
 
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum Move {
     Pass,
-    Drop(u8),
-    Jump(u8, u8),
+    Drop(Square),
+    Jump(Square, Square),
 }
 
 impl Move {
     #[must_use]
     pub const fn is_pass(&self) -> bool {
-        match self {
-            Move::Pass => true,
-            _ => false,
-        }
+        matches!(self, Move::Pass)
     }
 
     #[must_use]
     pub const fn is_single(&self) -> bool {
-        match self {
-            Move::Drop(_) => true,
-            _ => false,
-        }
+        matches!(self, Move::Drop(_))
     }
 
     #[must_use]
     pub const fn is_double(&self) -> bool {
-        match self {
-            Move::Jump(_, _) => true,
-            _ => false,
-        }
+        matches!(self, Move::Jump(_, _))
     }
 
     #[must_use]
-    pub const fn from(&self) -> Option<u8> {
+    pub const fn from(&self) -> Option<Square> {
         match self {
             Move::Pass => None,
             Move::Drop(_) => None,
@@ -43,7 +36,7 @@ impl Move {
     }
 
     #[must_use]
-    pub const fn to(&self) -> Option<u8> {
+    pub const fn to(&self) -> Option<Square> {
         match self {
             Move::Pass => None,
             Move::Drop(sq) => Some(*sq),
@@ -56,15 +49,80 @@ impl fmt::Display for Move {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Move::Pass => write!(f, "0000"),
-            Move::Drop(sq) => write!(f, "{}{}", (97 + (sq % 7)) as char, (49 + (sq / 7)) as char),
-            Move::Jump(fr, to) => write!(
-                f,
-                "{}{}{}{}",
-                (97 + (fr % 7)) as char,
-                (49 + (fr / 7)) as char,
-                (97 + (to % 7)) as char,
-                (49 + (to / 7)) as char
-            ),
+            Move::Drop(sq) => write!(f, "{}", sq),
+            Move::Jump(fr, to) => write!(f, "{}{}", fr, to),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveParseError {
+    InvalidFormat(String),
+    InvalidSquare(String),
+    SingleWrittenAsJump(String),
+    InvalidJump(String),
+    Illegal(Move),
+}
+
+impl fmt::Display for MoveParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveParseError::InvalidFormat(s) => write!(f, "unrecognised move format '{}'", s),
+            MoveParseError::InvalidSquare(s) => write!(f, "invalid square '{}'", s),
+            MoveParseError::SingleWrittenAsJump(s) => {
+                write!(
+                    f,
+                    "'{}' moves one square and should be written as a single",
+                    s
+                )
+            }
+            MoveParseError::InvalidJump(s) => {
+                write!(f, "'{}' does not move exactly two squares", s)
+            }
+            MoveParseError::Illegal(mv) => write!(f, "illegal move {}", mv),
+        }
+    }
+}
+
+impl Error for MoveParseError {}
+
+fn parse_square(s: &str) -> Result<Square, MoveParseError> {
+    s.parse()
+        .map_err(|_| MoveParseError::InvalidSquare(s.to_string()))
+}
+
+impl FromStr for Move {
+    type Err = MoveParseError;
+
+    /// Accepts "a1", "a1c3", "a1-c3", "0000" and "pass" in any case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim().to_ascii_lowercase();
+
+        if text == "0000" || text == "pass" {
+            return Ok(Move::Pass);
+        }
+
+        // Only the '-' between the two squares is removed
+        let squares = match text.len() {
+            5 if text.as_bytes()[2] == b'-' => format!("{}{}", &text[..2], &text[3..]),
+            2 | 4 => text,
+            _ => return Err(MoveParseError::InvalidFormat(s.to_string())),
+        };
+
+        if !squares.is_ascii() || !matches!(squares.len(), 2 | 4) {
+            return Err(MoveParseError::InvalidFormat(s.to_string()));
+        }
+
+        if squares.len() == 2 {
+            return Ok(Move::Drop(parse_square(&squares)?));
+        }
+
+        let fr = parse_square(&squares[0..2])?;
+        let to = parse_square(&squares[2..4])?;
+        match fr.distance(to) {
+            1 => Err(MoveParseError::SingleWrittenAsJump(s.to_string())),
+            2 => Ok(Move::Jump(fr, to)),
+            _ => Err(MoveParseError::InvalidJump(s.to_string())),
         }
     }
 }
@@ -76,21 +134,15 @@ mod tests {
     #[test]
     fn from() {
         assert_eq!(Move::Pass.from(), None);
-        assert_eq!(Move::Drop(Square::A1 as u8).from(), None);
-        assert_eq!(
-            Move::Jump(Square::A1 as u8, Square::A3 as u8).from(),
-            Some(Square::A1 as u8)
-        );
+        assert_eq!(Move::Drop(Square::A1).from(), None);
+        assert_eq!(Move::Jump(Square::A1, Square::A3).from(), Some(Square::A1));
     }
 
     #[test]
     fn to() {
         assert_eq!(Move::Pass.to(), None);
-        assert_eq!(Move::Drop(Square::A1 as u8).to(), Some(Square::A1 as u8));
-        assert_eq!(
-            Move::Jump(Square::A1 as u8, Square::A3 as u8).to(),
-            Some(Square::A3 as u8)
-        );
+        assert_eq!(Move::Drop(Square::A1).to(), Some(Square::A1));
+        assert_eq!(Move::Jump(Square::A1, Square::A3).to(), Some(Square::A3));
     }
 
     #[test]
@@ -99,12 +151,81 @@ mod tests {
         assert!(!Move::Pass.is_single());
         assert!(!Move::Pass.is_double());
 
-        assert!(!Move::Drop(Square::A1 as u8).is_pass());
-        assert!(Move::Drop(Square::A1 as u8).is_single());
-        assert!(!Move::Drop(Square::A1 as u8).is_double());
+        assert!(!Move::Drop(Square::A1).is_pass());
+        assert!(Move::Drop(Square::A1).is_single());
+        assert!(!Move::Drop(Square::A1).is_double());
+
+        assert!(!Move::Jump(Square::A1, Square::A3).is_pass());
+        assert!(!Move::Jump(Square::A1, Square::A3).is_single());
+        assert!(Move::Jump(Square::A1, Square::A3).is_double());
+    }
+
+    #[test]
+    fn from_str() {
+        let tests = [
+            ("0000", Move::Pass),
+            ("pass", Move::Pass),
+            ("PASS", Move::Pass),
+            ("a1", Move::Drop(Square::A1)),
+            ("G7", Move::Drop(Square::G7)),
+            ("d4", Move::Drop(Square::D4)),
+            ("a1c3", Move::Jump(Square::A1, Square::C3)),
+            ("A1C3", Move::Jump(Square::A1, Square::C3)),
+            ("a1-c3", Move::Jump(Square::A1, Square::C3)),
+            ("g7e6", Move::Jump(Square::G7, Square::E6)),
+        ];
+
+        for (text, mv) in tests {
+            assert_eq!(text.parse::<Move>(), Ok(mv));
+        }
+    }
 
-        assert!(!Move::Jump(Square::A1 as u8, Square::A3 as u8).is_pass());
-        assert!(!Move::Jump(Square::A1 as u8, Square::A3 as u8).is_single());
-        assert!(Move::Jump(Square::A1 as u8, Square::A3 as u8).is_double());
+    #[test]
+    fn from_str_errors() {
+        use crate::MoveParseError;
+
+        let tests = [
+            ("", MoveParseError::InvalidFormat("".to_string())),
+            ("a", MoveParseError::InvalidFormat("a".to_string())),
+            (
+                "a1c3e5",
+                MoveParseError::InvalidFormat("a1c3e5".to_string()),
+            ),
+            ("a1+c3", MoveParseError::InvalidFormat("a1+c3".to_string())),
+            ("h1", MoveParseError::InvalidSquare("h1".to_string())),
+            ("a8", MoveParseError::InvalidSquare("a8".to_string())),
+            ("a1a0", MoveParseError::InvalidSquare("a0".to_string())),
+            (
+                "a1b2",
+                MoveParseError::SingleWrittenAsJump("a1b2".to_string()),
+            ),
+            (
+                "a1-a2",
+                MoveParseError::SingleWrittenAsJump("a1-a2".to_string()),
+            ),
+            ("a1a1", MoveParseError::InvalidJump("a1a1".to_string())),
+            ("a1d1", MoveParseError::InvalidJump("a1d1".to_string())),
+            ("a1--3", MoveParseError::InvalidSquare("-3".to_string())),
+            ("a1-c-", MoveParseError::InvalidSquare("c-".to_string())),
+            ("a1---", MoveParseError::InvalidSquare("--".to_string())),
+            ("a1-é", MoveParseError::InvalidFormat("a1-é".to_string())),
+        ];
+
+        for (text, err) in tests {
+            assert_eq!(text.parse::<Move>(), Err(err));
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for fr in Square::ALL {
+            let mv = Move::Drop(fr);
+            assert_eq!(mv.to_string().parse::<Move>(), Ok(mv));
+
+            for to in crate::LUT_DOUBLES[fr as usize] {
+                let mv = Move::Jump(fr, to);
+                assert_eq!(mv.to_string().parse::<Move>(), Ok(mv));
+            }
+        }
     }
 }
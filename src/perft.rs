@@ -1,4 +1,9 @@
+use crate::bitboard::Bitboard;
 use crate::board::*;
+use crate::movelist::MoveList;
+use crate::moves::Move;
+use crate::tt::PerftTable;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[must_use]
 pub fn perft(pos: &Board, depth: u8) -> u64 {
@@ -10,7 +15,8 @@ pub fn perft(pos: &Board, depth: u8) -> u64 {
         return pos.count_pseudomoves();
     }
 
-    let moves = pos.pseudolegal_moves();
+    let mut moves = MoveList::new();
+    pos.generate_moves_into(&mut moves);
     let mut nodes: u64 = 0;
 
     for mv in &moves {
@@ -18,10 +24,192 @@ pub fn perft(pos: &Board, depth: u8) -> u64 {
         nodes += perft(&npos, depth - 1);
     }
 
-    if moves.is_empty() {
-        let npos: Board = pos.after_pass();
-        return perft(&npos, depth - 1);
+    nodes
+}
+
+/// What the moves made at one depth of `perft_stats` were
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct PerftStats {
+    /// The same as `perft` to this depth, and always singles + jumps + passes
+    pub nodes: u64,
+    pub singles: u64,
+    pub jumps: u64,
+    pub passes: u64,
+    /// Positions reached at this depth where the game is over
+    pub gameovers: u64,
+    /// Opponent pieces captured
+    pub flips: u64,
+}
+
+/// A breakdown of the moves at every depth from 1 to `depth`, the first entry is depth 1
+#[must_use]
+pub fn perft_stats(pos: &Board, depth: u8) -> Vec<PerftStats> {
+    let mut stats = vec![PerftStats::default(); depth as usize];
+    perft_stats_inner(pos, &mut stats);
+    stats
+}
+
+fn perft_stats_inner(pos: &Board, stats: &mut [PerftStats]) {
+    let Some((current, deeper)) = stats.split_first_mut() else {
+        return;
+    };
+
+    if pos.is_gameover() {
+        return;
+    }
+
+    let mut moves = MoveList::new();
+    pos.generate_moves_into(&mut moves);
+
+    for mv in &moves {
+        current.nodes += 1;
+        match mv {
+            Move::Pass => current.passes += 1,
+            Move::Drop(_) => current.singles += 1,
+            Move::Jump(_, _) => current.jumps += 1,
+        }
+        if let Some(to) = mv.to() {
+            current.flips += (Bitboard::from_square(to).singles() & pos.them()).count() as u64;
+        }
+
+        let npos = pos.after_move(mv);
+        if npos.is_gameover() {
+            current.gameovers += 1;
+        }
+        perft_stats_inner(&npos, deeper);
+    }
+}
+
+/// Node counts below each move, which add up to `perft` of the same depth
+///
+/// A forced pass is included like any other move, and there are no moves at depth 0
+/// or once the game is over.
+#[must_use]
+pub fn perft_divide(pos: &Board, depth: u8) -> Vec<(Move, u64)> {
+    if depth == 0 || pos.is_gameover() {
+        return Vec::new();
+    }
+
+    let mut moves = MoveList::new();
+    pos.generate_moves_into(&mut moves);
+
+    moves
+        .iter()
+        .map(|mv| (*mv, perft(&pos.after_move(mv), depth - 1)))
+        .collect()
+}
+
+/// Same as `perft`, with the work split between `threads` threads
+///
+/// Positions a few plies below the root are shared out one at a time, so threads that
+/// get small subtrees take more of them.
+#[must_use]
+pub fn perft_parallel(pos: &Board, depth: u8, threads: usize) -> u64 {
+    let threads = threads.max(1);
+    if threads == 1 || depth <= 2 {
+        return perft(pos, depth);
+    }
+
+    // Split until there are enough positions to keep every thread busy
+    let mut tasks = vec![*pos];
+    let mut remaining = depth;
+    while tasks.len() < threads * 16 && remaining > 2 {
+        let mut next = Vec::new();
+        let mut moves = MoveList::new();
+
+        for task in tasks.iter().filter(|task| !task.is_gameover()) {
+            moves.clear();
+            task.generate_moves_into(&mut moves);
+            next.extend(moves.iter().map(|mv| task.after_move(mv)));
+        }
+
+        tasks = next;
+        remaining -= 1;
+    }
+
+    let next_task = AtomicUsize::new(0);
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.min(tasks.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut nodes = 0;
+                    while let Some(task) = tasks.get(next_task.fetch_add(1, Ordering::Relaxed)) {
+                        nodes += perft(task, remaining);
+                    }
+                    nodes
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("perft worker panicked"))
+            .sum()
+    })
+}
+
+/// Node count from `perft_hashed` with how often the table helped
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct HashedPerft {
+    pub nodes: u64,
+    pub probes: u64,
+    pub hits: u64,
+}
+
+impl HashedPerft {
+    /// Fraction of probes that found a count, 0 if there were none
+    #[must_use]
+    pub fn hit_rate(&self) -> f64 {
+        if self.probes == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.probes as f64
+        }
+    }
+}
+
+/// Same as `perft`, reusing counts of positions already seen from `table`
+///
+/// Counts from earlier calls with the same table are reused too.
+#[must_use]
+pub fn perft_hashed(pos: &Board, depth: u8, table: &PerftTable) -> HashedPerft {
+    let mut result = HashedPerft::default();
+    result.nodes = perft_hashed_inner(pos, depth, table, &mut result);
+    result
+}
+
+fn perft_hashed_inner(pos: &Board, depth: u8, table: &PerftTable, stats: &mut HashedPerft) -> u64 {
+    if depth == 0 {
+        return 1;
+    } else if pos.is_gameover() {
+        return 0;
+    } else if depth == 1 {
+        return pos.count_pseudomoves();
+    }
+
+    // The hash ignores the halfmove clock, which only matters if it can reach the limit
+    let key = if pos.halfmoves() + depth as u32 >= 100 {
+        pos.hash() ^ (pos.halfmoves() as u64 + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+    } else {
+        pos.hash()
+    };
+
+    stats.probes += 1;
+    if let Some(nodes) = table.probe(key, depth) {
+        stats.hits += 1;
+        return nodes;
+    }
+
+    let mut moves = MoveList::new();
+    pos.generate_moves_into(&mut moves);
+    let mut nodes: u64 = 0;
+
+    for mv in &moves {
+        let npos = pos.after_move(mv);
+        nodes += perft_hashed_inner(&npos, depth - 1, table, stats);
     }
 
+    table.store(key, depth, nodes);
     nodes
 }
@@ -0,0 +1,576 @@
+use crate::board::Board;
+use crate::colour::Colour;
+use crate::fen::FenError;
+use crate::game::{Game, GameError};
+use crate::moves::{Move, MoveParseError};
+use crate::result::Outcome;
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+
+/// Tags every game has, written first and in this order
+pub const SEVEN_TAG_ROSTER: [&str; 7] =
+    ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+/// Movetext lines are wrapped before this many characters
+const LINE_WIDTH: usize = 80;
+
+/// A game as stored in PGN, tags, start position, moves, comments and result
+///
+/// The `Result`, `FEN` and `SetUp` tags are written from `outcome` and `start`,
+/// whatever is in `tags`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnGame {
+    /// Tags in the order they were read or set
+    pub tags: Vec<(String, String)>,
+    pub start: Board,
+    pub moves: Vec<Move>,
+    /// Comments and the number of moves played before each one
+    pub comments: Vec<(usize, String)>,
+    /// `None` for a game without a result, written as "*"
+    pub outcome: Option<Outcome>,
+}
+
+impl PgnGame {
+    /// A game with no tags or moves, missing roster tags are written as "?"
+    #[must_use]
+    pub fn new(start: Board) -> Self {
+        PgnGame {
+            tags: Vec::new(),
+            start,
+            moves: Vec::new(),
+            comments: Vec::new(),
+            outcome: None,
+        }
+    }
+
+    /// The moves and result of a `Game`, the result is `None` if it is not over
+    #[must_use]
+    pub fn from_game(game: &Game) -> Self {
+        PgnGame {
+            moves: game.moves().to_vec(),
+            outcome: game.result().map(|result| result.outcome),
+            ..PgnGame::new(*game.start())
+        }
+    }
+
+    /// Replays the moves, any illegal move is an error
+    pub fn to_game(&self) -> Result<Game, GameError> {
+        let mut game = Game::new(self.start);
+        for mv in &self.moves {
+            game.play(*mv)?;
+        }
+        Ok(game)
+    }
+
+    #[must_use]
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Replaces the value of a tag, or adds it if it is not there yet
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, old)) => *old = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// The position after every move has been played
+    #[must_use]
+    pub fn board(&self) -> Board {
+        let mut board = self.start;
+        for mv in &self.moves {
+            board.makemove(mv);
+        }
+        board
+    }
+
+    /// Tags are read a line at a time, so line breaks in the value are dropped
+    fn write_tag(f: &mut fmt::Formatter, name: &str, value: &str) -> fmt::Result {
+        let value: String = value
+            .chars()
+            .filter(|c| !matches!(c, '\n' | '\r'))
+            .collect();
+        let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+        writeln!(f, "[{} \"{}\"]", name, value)
+    }
+}
+
+impl fmt::Display for PgnGame {
+    /// Writes the game followed by a blank line, so games can be appended to a file
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for name in SEVEN_TAG_ROSTER {
+            match name {
+                "Result" => PgnGame::write_tag(f, name, outcome_str(self.outcome))?,
+                _ => PgnGame::write_tag(f, name, self.tag(name).unwrap_or("?"))?,
+            }
+        }
+        if self.start != Board::from_fen("startpos") {
+            PgnGame::write_tag(f, "SetUp", "1")?;
+            PgnGame::write_tag(f, "FEN", &self.start.get_fen())?;
+        }
+        for (name, value) in &self.tags {
+            if !SEVEN_TAG_ROSTER.contains(&name.as_str()) && name != "SetUp" && name != "FEN" {
+                PgnGame::write_tag(f, name, value)?;
+            }
+        }
+        writeln!(f)?;
+
+        let mut tokens = Vec::new();
+        let mut comments = self.comments.iter().peekable();
+        let mut board = self.start;
+        let mut after_comment = true;
+
+        for (idx, mv) in self.moves.iter().enumerate() {
+            while let Some((_, text)) = comments.next_if(|(ply, _)| *ply <= idx) {
+                tokens.push(comment(text));
+                after_comment = true;
+            }
+
+            match board.turn() {
+                Colour::Black => tokens.push(format!("{}.", board.fullmoves())),
+                Colour::White if after_comment => tokens.push(format!("{}...", board.fullmoves())),
+                Colour::White => {}
+            }
+            tokens.push(mv.to_string());
+            board.makemove(mv);
+            after_comment = false;
+        }
+        for (_, text) in comments {
+            tokens.push(comment(text));
+        }
+        tokens.push(outcome_str(self.outcome).to_string());
+
+        let mut width = 0;
+        for token in tokens {
+            if width > 0 && width + 1 + token.len() >= LINE_WIDTH {
+                writeln!(f)?;
+                width = 0;
+            }
+            if width > 0 {
+                write!(f, " ")?;
+                width += 1;
+            }
+            write!(f, "{}", token)?;
+            width += token.len();
+        }
+        writeln!(f)?;
+        writeln!(f)
+    }
+}
+
+/// A comment in braces, any '}' in the text would end it early and is dropped
+fn comment(text: &str) -> String {
+    format!("{{{}}}", text.replace('}', ""))
+}
+
+/// "1-0", "0-1", "1/2-1/2" or "*", White's score is written first
+#[must_use]
+pub const fn outcome_str(outcome: Option<Outcome>) -> &'static str {
+    match outcome {
+        Some(Outcome::WhiteWin) => "1-0",
+        Some(Outcome::BlackWin) => "0-1",
+        Some(Outcome::Draw) => "1/2-1/2",
+        None => "*",
+    }
+}
+
+/// The inverse of `outcome_str`, `None` if the text is not a result at all
+fn parse_outcome(text: &str) -> Option<Option<Outcome>> {
+    match text {
+        "1-0" => Some(Some(Outcome::WhiteWin)),
+        "0-1" => Some(Some(Outcome::BlackWin)),
+        "1/2-1/2" => Some(Some(Outcome::Draw)),
+        "*" => Some(None),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PgnErrorKind {
+    /// Reading failed, the message of the underlying `io::Error`
+    Io(String),
+    InvalidTag(String),
+    Fen(FenError),
+    InvalidMove(MoveParseError),
+    /// A move played after the game was already over
+    MoveAfterGameOver(Move),
+    UnterminatedComment,
+    UnexpectedToken(String),
+}
+
+impl fmt::Display for PgnErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PgnErrorKind::Io(e) => write!(f, "{}", e),
+            PgnErrorKind::InvalidTag(s) => write!(f, "invalid tag '{}'", s),
+            PgnErrorKind::Fen(e) => write!(f, "{}", e),
+            PgnErrorKind::InvalidMove(e) => write!(f, "{}", e),
+            PgnErrorKind::MoveAfterGameOver(mv) => write!(f, "move {} after the game is over", mv),
+            PgnErrorKind::UnterminatedComment => write!(f, "comment is never closed"),
+            PgnErrorKind::UnexpectedToken(s) => write!(f, "unexpected token '{}'", s),
+        }
+    }
+}
+
+/// A `PgnErrorKind` and where it was found, lines and columns count from 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: PgnErrorKind,
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {} column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl Error for PgnError {}
+
+/// What to do with lines after an error, so the next game can still be read
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Skip {
+    Nothing,
+    /// The error was in the tags, skip them and then the movetext
+    Tags,
+    Movetext,
+}
+
+/// The movetext of the game being read
+struct Movetext {
+    board: Board,
+    moves: Vec<Move>,
+    comments: Vec<(usize, String)>,
+    /// Text so far, line and column of a comment that continues onto the next line
+    open_comment: Option<(String, usize, usize)>,
+    outcome: Option<Option<Outcome>>,
+}
+
+/// Reads games one at a time from a PGN file
+///
+/// Only the game being read is kept in memory. After an error the rest of that game
+/// is skipped, so iterating carries on with the next one.
+pub struct PgnReader<R> {
+    reader: R,
+    line: usize,
+    /// A line read past the end of the previous game
+    pending: Option<String>,
+    skip: Skip,
+}
+
+impl<R: BufRead> PgnReader<R> {
+    #[must_use]
+    pub fn new(reader: R) -> Self {
+        PgnReader {
+            reader,
+            line: 0,
+            pending: None,
+            skip: Skip::Nothing,
+        }
+    }
+
+    /// Reads the next game, or returns `None` once there are no more
+    pub fn read_game(&mut self) -> Option<Result<PgnGame, PgnError>> {
+        let mut tags: Vec<(String, String)> = Vec::new();
+        let mut movetext: Option<Movetext> = None;
+
+        loop {
+            let line = match self.next_line() {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(e) => return Some(Err(e)),
+            };
+            let trimmed = line.trim();
+            let is_tag = trimmed.starts_with('[');
+
+            match self.skip {
+                Skip::Tags if is_tag || trimmed.is_empty() => continue,
+                Skip::Tags | Skip::Movetext if !is_tag => {
+                    self.skip = Skip::Movetext;
+                    continue;
+                }
+                _ => self.skip = Skip::Nothing,
+            }
+
+            let in_comment = movetext
+                .as_ref()
+                .is_some_and(|movetext| movetext.open_comment.is_some());
+
+            if is_tag && !in_comment {
+                if movetext.is_some() {
+                    // A game without a result, this line belongs to the next one
+                    self.pending = Some(line);
+                    break;
+                }
+                let column = 1 + line.len() - line.trim_start().len();
+                match parse_tag(trimmed) {
+                    Some((name, value)) if name == "FEN" => {
+                        if let Err(e) = Board::try_from_fen(&value) {
+                            return Some(Err(self.error(column, PgnErrorKind::Fen(e))));
+                        }
+                        tags.push((name, value));
+                    }
+                    Some(tag) => tags.push(tag),
+                    None => {
+                        return Some(Err(
+                            self.error(column, PgnErrorKind::InvalidTag(trimmed.to_string()))
+                        ))
+                    }
+                }
+                continue;
+            }
+
+            if trimmed.is_empty() && !in_comment {
+                continue;
+            }
+
+            let movetext = match &mut movetext {
+                Some(movetext) => movetext,
+                None => movetext.insert(Movetext {
+                    board: start_position(&tags),
+                    moves: Vec::new(),
+                    comments: Vec::new(),
+                    open_comment: None,
+                    outcome: None,
+                }),
+            };
+
+            if let Err((column, kind)) = parse_movetext(&line, movetext, self.line) {
+                return Some(Err(self.error(column, kind)));
+            }
+            if movetext.outcome.is_some() {
+                break;
+            }
+        }
+
+        if let Some(Movetext {
+            open_comment: Some((_, line, column)),
+            ..
+        }) = movetext
+        {
+            return Some(Err(PgnError {
+                line,
+                column,
+                kind: PgnErrorKind::UnterminatedComment,
+            }));
+        }
+
+        match movetext {
+            Some(movetext) => {
+                let outcome = match movetext.outcome {
+                    Some(outcome) => outcome,
+                    None => tag_outcome(&tags),
+                };
+                Some(Ok(PgnGame {
+                    start: start_position(&tags),
+                    tags,
+                    moves: movetext.moves,
+                    comments: movetext.comments,
+                    outcome,
+                }))
+            }
+            // Tags without any movetext are still a game, just one without moves
+            None if !tags.is_empty() => Some(Ok(PgnGame {
+                start: start_position(&tags),
+                outcome: tag_outcome(&tags),
+                tags,
+                moves: Vec::new(),
+                comments: Vec::new(),
+            })),
+            None => None,
+        }
+    }
+
+    fn next_line(&mut self) -> Result<Option<String>, PgnError> {
+        if let Some(line) = self.pending.take() {
+            return Ok(Some(line));
+        }
+
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) => Ok(None),
+            Ok(_) => {
+                self.line += 1;
+                Ok(Some(line.trim_end_matches(['\n', '\r']).to_string()))
+            }
+            Err(e) => Err(self.error(1, PgnErrorKind::Io(e.to_string()))),
+        }
+    }
+
+    /// An error on the current line, the rest of the game is skipped
+    fn error(&mut self, column: usize, kind: PgnErrorKind) -> PgnError {
+        self.skip = match kind {
+            PgnErrorKind::InvalidTag(_) | PgnErrorKind::Fen(_) => Skip::Tags,
+            _ => Skip::Movetext,
+        };
+        PgnError {
+            line: self.line,
+            column,
+            kind,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_game()
+    }
+}
+
+/// Parses `[Name "value"]`
+fn parse_tag(line: &str) -> Option<(String, String)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?.trim();
+    let (name, value) = inner.split_once(char::is_whitespace)?;
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.push(chars.next()?),
+            '"' => return None,
+            _ => unescaped.push(c),
+        }
+    }
+
+    Some((name.to_string(), unescaped))
+}
+
+/// The `FEN` tag if there is one, which is checked as it is read
+fn start_position(tags: &[(String, String)]) -> Board {
+    match tags.iter().find(|(name, _)| name == "FEN") {
+        Some((_, fen)) => Board::from_fen(fen),
+        None => Board::from_fen("startpos"),
+    }
+}
+
+fn tag_outcome(tags: &[(String, String)]) -> Option<Outcome> {
+    tags.iter()
+        .find(|(name, _)| name == "Result")
+        .and_then(|(_, value)| parse_outcome(value))
+        .flatten()
+}
+
+/// Plays the moves on one line of movetext, errors come with their column
+fn parse_movetext(
+    line: &str,
+    movetext: &mut Movetext,
+    number: usize,
+) -> Result<(), (usize, PgnErrorKind)> {
+    let mut chars = line.char_indices().peekable();
+
+    if let Some((mut text, start_line, start_column)) = movetext.open_comment.take() {
+        loop {
+            match chars.next() {
+                Some((_, '}')) => break,
+                Some((_, c)) => text.push(c),
+                None => {
+                    text.push(' ');
+                    movetext.open_comment = Some((text, start_line, start_column));
+                    return Ok(());
+                }
+            }
+        }
+        movetext
+            .comments
+            .push((movetext.moves.len(), text.trim().to_string()));
+    }
+
+    while let Some(&(idx, c)) = chars.peek() {
+        let column = line[..idx].chars().count() + 1;
+
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        if movetext.outcome.is_some() {
+            let rest = line[idx..].split_whitespace().next().unwrap_or("");
+            return Err((column, PgnErrorKind::UnexpectedToken(rest.to_string())));
+        }
+
+        // Comments
+        if c == '{' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '}')) => break,
+                    Some((_, c)) => text.push(c),
+                    None => {
+                        text.push(' ');
+                        movetext.open_comment = Some((text, number, column));
+                        return Ok(());
+                    }
+                }
+            }
+            movetext
+                .comments
+                .push((movetext.moves.len(), text.trim().to_string()));
+            continue;
+        }
+        if c == ';' {
+            let text = line[idx + 1..].trim();
+            movetext
+                .comments
+                .push((movetext.moves.len(), text.to_string()));
+            return Ok(());
+        }
+
+        let end = line[idx..]
+            .find(|c: char| c.is_whitespace() || c == '{' || c == ';')
+            .map_or(line.len(), |len| idx + len);
+        let token = &line[idx..end];
+        while chars.peek().is_some_and(|&(i, _)| i < end) {
+            chars.next();
+        }
+
+        if let Some(outcome) = parse_outcome(token) {
+            movetext.outcome = Some(outcome);
+            continue;
+        }
+
+        // Move numbers, either on their own or joined to the move as in "1.b6"
+        let digits = token.len() - token.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let after_number = token[digits..].trim_start_matches('.');
+        let (token, column) = if digits > 0 && after_number.len() < token.len() - digits {
+            if after_number.is_empty() {
+                continue;
+            }
+            (after_number, column + token.len() - after_number.len())
+        } else {
+            (token, column)
+        };
+
+        let mv = token
+            .parse::<Move>()
+            .map_err(|e| (column, PgnErrorKind::InvalidMove(e)))?;
+        if movetext.board.is_gameover() {
+            return Err((column, PgnErrorKind::MoveAfterGameOver(mv)));
+        }
+        if !movetext.board.is_legal_move(&mv) {
+            let e = MoveParseError::Illegal(mv);
+            return Err((column, PgnErrorKind::InvalidMove(e)));
+        }
+        movetext.board.makemove(&mv);
+        movetext.moves.push(mv);
+    }
+
+    Ok(())
+}
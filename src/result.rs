@@ -0,0 +1,51 @@
+use crate::colour::Colour;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    BlackWin,
+    WhiteWin,
+    Draw,
+}
+
+/// Why a game ended
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Termination {
+    /// One or both sides have no pieces left
+    NoPieces,
+    /// Neither side has a move, so the side with more pieces wins
+    NoMoves,
+    /// 100 halfmoves without a single move
+    HalfmoveLimit,
+    /// The same position occurred three times, only detected by `Game`
+    Repetition,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct GameResult {
+    pub outcome: Outcome,
+    pub termination: Termination,
+}
+
+impl GameResult {
+    #[must_use]
+    pub const fn new(outcome: Outcome, termination: Termination) -> Self {
+        GameResult {
+            outcome,
+            termination,
+        }
+    }
+
+    #[must_use]
+    pub const fn winner(&self) -> Option<Colour> {
+        match self.outcome {
+            Outcome::BlackWin => Some(Colour::Black),
+            Outcome::WhiteWin => Some(Colour::White),
+            Outcome::Draw => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_draw(&self) -> bool {
+        matches!(self.outcome, Outcome::Draw)
+    }
+}
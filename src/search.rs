@@ -0,0 +1,483 @@
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::eval::{Evaluator, HandcraftedEval};
+use crate::movelist::{MoveList, MAX_MOVES};
+use crate::moves::Move;
+use crate::squares::Square;
+use crate::timeman::{Clocks, SystemClock, TimeManager};
+use crate::tt::{Bound, TTEntry, TranspositionTable};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Larger than any score a search can return
+pub const INF: i32 = 32_000;
+
+/// Score for winning at the root, wins found further away score lower
+pub const WIN_SCORE: i32 = 30_000;
+
+pub const MAX_DEPTH: u8 = 64;
+
+/// How much a win loses for every ply further away, more than any piece difference
+const PLY_WEIGHT: i32 = 64;
+
+/// Scores this close to `WIN_SCORE` are wins found at a known distance
+const WIN_BOUND: i32 = WIN_SCORE - PLY_WEIGHT * (MAX_DEPTH as i32 + 1);
+
+/// When a search should stop, with no limits set it stops after `MAX_DEPTH`
+#[derive(Debug, Clone, Default)]
+pub struct SearchLimits {
+    pub depth: Option<u8>,
+    pub nodes: Option<u64>,
+    pub movetime: Option<Duration>,
+    pub infinite: bool,
+    pub stop: Arc<AtomicBool>,
+    /// Only search these root moves, all of them when empty
+    pub searchmoves: Vec<Move>,
+    /// Budget the time from the clocks, ignored if `movetime` is set
+    pub clocks: Option<Clocks>,
+}
+
+/// The result of the last completed iteration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    pub best_move: Option<Move>,
+    pub score: i32,
+    pub depth: u8,
+    pub pv: Vec<Move>,
+    pub nodes: u64,
+    pub time: Duration,
+    pub hashfull: usize,
+}
+
+struct Search<'a, E> {
+    limits: &'a SearchLimits,
+    tt: &'a TranspositionTable,
+    eval: &'a E,
+    start: Instant,
+    time: Option<TimeManager>,
+    nodes: u64,
+    stopped: bool,
+    can_stop: bool,
+    pv_move: Option<Move>,
+}
+
+impl<E: Evaluator> Search<'_, E> {
+    fn should_stop(&mut self) -> bool {
+        if !self.stopped && self.can_stop {
+            let over_nodes = match self.limits.nodes {
+                Some(nodes) => !self.limits.infinite && self.nodes >= nodes,
+                None => false,
+            };
+
+            if over_nodes || self.nodes & 1023 == 0 {
+                self.check_limits();
+            }
+        }
+
+        self.stopped
+    }
+
+    fn check_limits(&mut self) {
+        if self.limits.stop.load(Ordering::Relaxed) {
+            self.stopped = true;
+        } else if !self.limits.infinite {
+            if let Some(nodes) = self.limits.nodes {
+                self.stopped |= self.nodes >= nodes;
+            }
+            if let Some(movetime) = self.limits.movetime {
+                self.stopped |= self.start.elapsed() >= movetime;
+            }
+            if let Some(time) = &self.time {
+                self.stopped |= time.should_stop();
+            }
+        }
+    }
+
+    fn negamax(
+        &mut self,
+        pos: &Board,
+        mut alpha: i32,
+        beta: i32,
+        depth: u8,
+        ply: i32,
+        pv: &mut Vec<Move>,
+    ) -> i32 {
+        pv.clear();
+        self.nodes += 1;
+
+        if self.should_stop() {
+            return 0;
+        }
+
+        if pos.is_gameover() {
+            return terminal_score(pos, ply);
+        }
+
+        if depth == 0 {
+            return self.eval.eval(pos);
+        }
+
+        let entry = self.tt.probe(pos.hash());
+        let tt_move = entry.and_then(|entry| entry.mv);
+
+        // The root always searches so that it has a best move and a full PV
+        if let Some(entry) = entry.filter(|entry| ply > 0 && entry.depth >= depth) {
+            let score = score_from_tt(entry.score, ply);
+            let cutoff = match entry.bound {
+                Bound::Exact => true,
+                Bound::Lower => score >= beta,
+                Bound::Upper => score <= alpha,
+            };
+
+            if cutoff {
+                return score;
+            }
+        }
+
+        let mut moves = MoveList::new();
+        pos.generate_moves_into(&mut moves);
+        let first = if ply == 0 {
+            self.pv_move.or(tt_move)
+        } else {
+            tt_move
+        };
+        let order = order_moves(pos, &moves, first);
+
+        let alpha_orig = alpha;
+        let mut best = -INF;
+        let mut best_move = None;
+        let mut child_pv = Vec::new();
+
+        for &idx in &order[..moves.len()] {
+            let mv = moves[idx as usize];
+
+            if ply == 0
+                && !self.limits.searchmoves.is_empty()
+                && !self.limits.searchmoves.contains(&mv)
+            {
+                continue;
+            }
+
+            let npos = pos.after_move(&mv);
+            let score = -self.negamax(&npos, -beta, -alpha, depth - 1, ply + 1, &mut child_pv);
+
+            if self.stopped {
+                return 0;
+            }
+
+            if score > best {
+                best = score;
+                best_move = Some(mv);
+
+                if score > alpha {
+                    alpha = score;
+                    pv.clear();
+                    pv.push(mv);
+                    pv.extend_from_slice(&child_pv);
+                }
+
+                if score >= beta {
+                    break;
+                }
+            }
+        }
+
+        let bound = if best >= beta {
+            Bound::Lower
+        } else if best > alpha_orig {
+            Bound::Exact
+        } else {
+            Bound::Upper
+        };
+
+        self.tt.store(
+            pos.hash(),
+            TTEntry {
+                depth,
+                score: score_to_tt(best, ply),
+                bound,
+                mv: best_move,
+            },
+        );
+
+        best
+    }
+}
+
+/// Plies until a won game ends, negative for a lost one and `None` if the score is not a result
+#[must_use]
+pub const fn win_distance(score: i32) -> Option<i32> {
+    if score > WIN_BOUND {
+        Some((WIN_SCORE - score + PLY_WEIGHT - 1) / PLY_WEIGHT)
+    } else if score < -WIN_BOUND {
+        Some(-((WIN_SCORE + score + PLY_WEIGHT - 1) / PLY_WEIGHT))
+    } else {
+        None
+    }
+}
+
+/// Wins are stored relative to the position rather than the root
+const fn score_to_tt(score: i32, ply: i32) -> i32 {
+    if score > WIN_BOUND {
+        score + ply * PLY_WEIGHT
+    } else if score < -WIN_BOUND {
+        score - ply * PLY_WEIGHT
+    } else {
+        score
+    }
+}
+
+const fn score_from_tt(score: i32, ply: i32) -> i32 {
+    if score > WIN_BOUND {
+        score - ply * PLY_WEIGHT
+    } else if score < -WIN_BOUND {
+        score + ply * PLY_WEIGHT
+    } else {
+        score
+    }
+}
+
+/// Score of a finished game from the point of view of the side to move
+///
+/// Sooner wins score higher, then bigger piece differences. Draws score 0.
+fn terminal_score(pos: &Board, ply: i32) -> i32 {
+    match pos.result().and_then(|result| result.winner()) {
+        Some(colour) if colour == pos.turn() => WIN_SCORE - ply * PLY_WEIGHT + pos.material(),
+        Some(_) => -WIN_SCORE + ply * PLY_WEIGHT + pos.material(),
+        None => 0,
+    }
+}
+
+/// Indices into `moves`, best first: the previous best move, then by pieces gained
+fn order_moves(pos: &Board, moves: &MoveList, first: Option<Move>) -> [u8; MAX_MOVES] {
+    let mut scores = [0i32; MAX_MOVES];
+    let mut order = [0u8; MAX_MOVES];
+
+    for (idx, mv) in moves.iter().enumerate() {
+        order[idx] = idx as u8;
+        scores[idx] = match mv {
+            _ if Some(*mv) == first => 1000,
+            Move::Pass => 0,
+            Move::Drop(sq) => 1 + captures(pos, *sq),
+            Move::Jump(_, to) => captures(pos, *to),
+        };
+    }
+
+    order[..moves.len()].sort_by_key(|&idx| -scores[idx as usize]);
+    order
+}
+
+fn captures(pos: &Board, sq: Square) -> i32 {
+    (Bitboard::from_square(sq).singles() & pos.them()).count()
+}
+
+/// Searches with iterative deepening until a limit is reached, using a new table
+#[must_use]
+pub fn search(pos: &Board, limits: &SearchLimits) -> SearchResult {
+    search_with_info(pos, limits, &TranspositionTable::default(), |_| {})
+}
+
+/// Like `search`, sharing `tt` and calling `info` after every completed iteration
+pub fn search_with_info<F: FnMut(&SearchResult)>(
+    pos: &Board,
+    limits: &SearchLimits,
+    tt: &TranspositionTable,
+    info: F,
+) -> SearchResult {
+    search_with_eval(pos, limits, tt, &HandcraftedEval::default(), 1, info)
+}
+
+/// Like `search_with_info`, with `threads - 1` helpers searching alongside in the same table
+///
+/// Helpers search deeper than the main thread to fill the table with useful entries,
+/// stop when it does, and vote on the best move. Every thread keeps to the limits and
+/// the node limit is for all of them together. `info` is only called by the main
+/// thread, and a single thread gives exactly the result of `search_with_info`.
+pub fn search_threads<F: FnMut(&SearchResult)>(
+    pos: &Board,
+    limits: &SearchLimits,
+    tt: &TranspositionTable,
+    threads: usize,
+    info: F,
+) -> SearchResult {
+    search_with_eval(pos, limits, tt, &HandcraftedEval::default(), threads, info)
+}
+
+/// Like `search_threads`, scoring the leaves with `eval` instead of the default `HandcraftedEval`
+pub fn search_with_eval<E: Evaluator + Sync, F: FnMut(&SearchResult)>(
+    pos: &Board,
+    limits: &SearchLimits,
+    tt: &TranspositionTable,
+    eval: &E,
+    threads: usize,
+    info: F,
+) -> SearchResult {
+    tt.new_search();
+
+    if threads <= 1 {
+        return iterate(pos, limits, tt, eval, 0, info);
+    }
+
+    // Threads split the node budget, helpers get their own stop flag for when the main one is done
+    let nodes = limits.nodes.map(|nodes| (nodes / threads as u64).max(1));
+    let main_limits = SearchLimits {
+        nodes,
+        ..limits.clone()
+    };
+    let helper_limits = SearchLimits {
+        nodes,
+        stop: Arc::new(AtomicBool::new(false)),
+        ..limits.clone()
+    };
+
+    std::thread::scope(|scope| {
+        let helpers: Vec<_> = (1..threads)
+            .map(|id| {
+                let limits = &helper_limits;
+                let offset = 1 + (id as u8 - 1) % 2;
+                scope.spawn(move || iterate(pos, limits, tt, eval, offset, |_| {}))
+            })
+            .collect();
+
+        let main = iterate(pos, &main_limits, tt, eval, 0, info);
+        helper_limits.stop.store(true, Ordering::Relaxed);
+
+        let mut results = vec![main];
+        for helper in helpers {
+            results.push(helper.join().expect("search helper panicked"));
+        }
+
+        vote(&results)
+    })
+}
+
+/// Picks the move with the most votes, each weighted by its depth and score
+///
+/// Only threads that got at least as deep as the main thread vote, so helpers that
+/// were stopped early cannot outvote it. The result is the deepest one for the
+/// winning move, with the nodes of every thread.
+fn vote(results: &[SearchResult]) -> SearchResult {
+    let main = &results[0];
+    let voters: Vec<&SearchResult> = results.iter().filter(|r| r.depth >= main.depth).collect();
+    let worst = voters.iter().map(|r| r.score).min().unwrap_or(0);
+    let mut votes: Vec<(Move, i64)> = Vec::new();
+
+    for result in &voters {
+        if let Some(mv) = result.best_move {
+            let weight = (result.score - worst + 10) as i64 * result.depth as i64;
+            match votes.iter_mut().find(|(m, _)| *m == mv) {
+                Some((_, total)) => *total += weight,
+                None => votes.push((mv, weight)),
+            }
+        }
+    }
+
+    // Ties go to the move found first, which is the main thread's if it has one
+    let mut winner = main.best_move;
+    let mut most = i64::MIN;
+    for (mv, total) in votes {
+        if total > most {
+            most = total;
+            winner = Some(mv);
+        }
+    }
+
+    let mut best = main;
+    for result in voters {
+        if result.best_move == winner && (best.best_move != winner || result.depth > best.depth) {
+            best = result;
+        }
+    }
+
+    SearchResult {
+        nodes: results.iter().map(|r| r.nodes).sum(),
+        time: main.time,
+        hashfull: main.hashfull,
+        ..best.clone()
+    }
+}
+
+/// Iterative deepening, each iteration searching `offset` plies deeper than its number
+///
+/// Helpers have an offset and may stop before finishing an iteration, since their
+/// results are only votes.
+fn iterate<E: Evaluator, F: FnMut(&SearchResult)>(
+    pos: &Board,
+    limits: &SearchLimits,
+    tt: &TranspositionTable,
+    eval: &E,
+    offset: u8,
+    mut info: F,
+) -> SearchResult {
+    let mut search = Search {
+        limits,
+        tt,
+        eval,
+        start: Instant::now(),
+        time: match (limits.movetime, &limits.clocks) {
+            (None, Some(clocks)) => Some(TimeManager::new(SystemClock::new(), clocks, pos)),
+            _ => None,
+        },
+        nodes: 0,
+        stopped: false,
+        can_stop: offset > 0,
+        pv_move: None,
+    };
+    let mut result = SearchResult {
+        best_move: None,
+        score: 0,
+        depth: 0,
+        pv: Vec::new(),
+        nodes: 0,
+        time: Duration::ZERO,
+        hashfull: 0,
+    };
+
+    if pos.is_gameover() {
+        result.score = terminal_score(pos, 0);
+        return result;
+    }
+
+    let max_depth = limits.depth.unwrap_or(MAX_DEPTH).clamp(1, MAX_DEPTH);
+    let mut pv = Vec::new();
+
+    for iteration in 1..=max_depth {
+        let depth = (iteration + offset).min(max_depth);
+        let score = search.negamax(pos, -INF, INF, depth, 0, &mut pv);
+
+        // Depth 1 always completes, results from later unfinished iterations are discarded
+        if search.stopped {
+            break;
+        }
+
+        result.best_move = pv.first().copied();
+        result.score = score;
+        result.depth = depth;
+        result.pv = pv.clone();
+        result.nodes = search.nodes;
+        result.time = search.start.elapsed();
+        result.hashfull = tt.hashfull();
+        search.pv_move = result.best_move;
+
+        info(&result);
+
+        search.can_stop = true;
+        search.check_limits();
+        if search.stopped || depth == max_depth {
+            break;
+        }
+
+        if let Some(time) = &mut search.time {
+            time.update(result.best_move);
+            if !limits.infinite && !time.should_continue() {
+                break;
+            }
+        }
+    }
+
+    result.nodes = search.nodes;
+    result.time = search.start.elapsed();
+    result.hashfull = tt.hashfull();
+    result
+}
@@ -1,6 +1,10 @@
-#[allow(dead_code)]
+use crate::bitboard::{Bitboard, BitboardIter};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
 #[rustfmt::skip]
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u8)]
 pub enum Square {
     A1, B1, C1, D1, E1, F1, G1,
@@ -11,3 +15,173 @@ pub enum Square {
     A6, B6, C6, D6, E6, F6, G6,
     A7, B7, C7, D7, E7, F7, G7,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SquareError {
+    IndexOutOfRange(u8),
+    InvalidName(String),
+}
+
+impl fmt::Display for SquareError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SquareError::IndexOutOfRange(idx) => write!(f, "square index {} is not below 49", idx),
+            SquareError::InvalidName(s) => write!(f, "invalid square '{}'", s),
+        }
+    }
+}
+
+impl Error for SquareError {}
+
+impl Square {
+    #[rustfmt::skip]
+    pub const ALL: [Square; 49] = [
+        Square::A1, Square::B1, Square::C1, Square::D1, Square::E1, Square::F1, Square::G1,
+        Square::A2, Square::B2, Square::C2, Square::D2, Square::E2, Square::F2, Square::G2,
+        Square::A3, Square::B3, Square::C3, Square::D3, Square::E3, Square::F3, Square::G3,
+        Square::A4, Square::B4, Square::C4, Square::D4, Square::E4, Square::F4, Square::G4,
+        Square::A5, Square::B5, Square::C5, Square::D5, Square::E5, Square::F5, Square::G5,
+        Square::A6, Square::B6, Square::C6, Square::D6, Square::E6, Square::F6, Square::G6,
+        Square::A7, Square::B7, Square::C7, Square::D7, Square::E7, Square::F7, Square::G7,
+    ];
+
+    /// Files and ranks both count from 0, so a1 is (0, 0) and g7 is (6, 6)
+    #[must_use]
+    pub const fn from_coords(file: u8, rank: u8) -> Option<Self> {
+        if file < 7 && rank < 7 {
+            Some(Square::ALL[(7 * rank + file) as usize])
+        } else {
+            None
+        }
+    }
+
+    #[must_use]
+    pub const fn index(self) -> u8 {
+        self as u8
+    }
+
+    #[must_use]
+    pub const fn file(self) -> u8 {
+        self as u8 % 7
+    }
+
+    #[must_use]
+    pub const fn rank(self) -> u8 {
+        self as u8 / 7
+    }
+
+    /// Number of king steps between two squares, 1 for a single and 2 for a jump
+    #[must_use]
+    pub const fn distance(self, other: Square) -> u8 {
+        let dx = self.file().abs_diff(other.file());
+        let dy = self.rank().abs_diff(other.rank());
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+
+    /// The up to 8 squares adjacent to this one
+    #[must_use]
+    pub const fn neighbours(self) -> BitboardIter {
+        BitboardIter::new(Bitboard::from_square(self).singles())
+    }
+}
+
+impl TryFrom<u8> for Square {
+    type Error = SquareError;
+
+    fn try_from(idx: u8) -> Result<Self, Self::Error> {
+        match Square::ALL.get(idx as usize) {
+            Some(sq) => Ok(*sq),
+            None => Err(SquareError::IndexOutOfRange(idx)),
+        }
+    }
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            (b'a' + self.file()) as char,
+            (b'1' + self.rank()) as char
+        )
+    }
+}
+
+impl FromStr for Square {
+    type Err = SquareError;
+
+    /// Accepts names such as "d4", the file may be upper case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes() {
+            [file @ (b'a'..=b'g' | b'A'..=b'G'), rank @ b'1'..=b'7'] => {
+                Ok(Square::ALL[(7 * (rank - b'1') + (file.to_ascii_lowercase() - b'a')) as usize])
+            }
+            _ => Err(SquareError::InvalidName(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Square, SquareError};
+
+    #[test]
+    fn coords() {
+        assert_eq!(Square::from_coords(0, 0), Some(Square::A1));
+        assert_eq!(Square::from_coords(3, 3), Some(Square::D4));
+        assert_eq!(Square::from_coords(6, 6), Some(Square::G7));
+        assert_eq!(Square::from_coords(7, 0), None);
+        assert_eq!(Square::from_coords(0, 7), None);
+
+        for sq in Square::ALL {
+            assert_eq!(Square::from_coords(sq.file(), sq.rank()), Some(sq));
+        }
+    }
+
+    #[test]
+    fn try_from() {
+        assert_eq!(Square::try_from(0), Ok(Square::A1));
+        assert_eq!(Square::try_from(48), Ok(Square::G7));
+        assert_eq!(Square::try_from(49), Err(SquareError::IndexOutOfRange(49)));
+        assert_eq!(
+            Square::try_from(255),
+            Err(SquareError::IndexOutOfRange(255))
+        );
+
+        for (idx, sq) in Square::ALL.iter().enumerate() {
+            assert_eq!(sq.index() as usize, idx);
+        }
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(Square::A1.to_string(), "a1");
+        assert_eq!(Square::D4.to_string(), "d4");
+        assert_eq!(Square::G7.to_string(), "g7");
+        assert_eq!("D4".parse::<Square>(), Ok(Square::D4));
+        assert!("h1".parse::<Square>().is_err());
+        assert!("a8".parse::<Square>().is_err());
+        assert!("a".parse::<Square>().is_err());
+        assert!("a10".parse::<Square>().is_err());
+
+        for sq in Square::ALL {
+            assert_eq!(sq.to_string().parse::<Square>(), Ok(sq));
+        }
+    }
+
+    #[test]
+    fn neighbours() {
+        let a1: Vec<Square> = Square::A1.neighbours().collect();
+        assert_eq!(a1, vec![Square::B1, Square::A2, Square::B2]);
+        assert_eq!(Square::D4.neighbours().count(), 8);
+        assert_eq!(Square::G4.neighbours().count(), 5);
+
+        for sq in Square::ALL {
+            assert!(sq.neighbours().all(|n| n.distance(sq) == 1));
+        }
+    }
+}
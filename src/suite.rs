@@ -0,0 +1,191 @@
+use crate::board::Board;
+use crate::fen::FenError;
+use crate::perft::perft;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The standard layouts with Black to move and their perft counts to depth 6
+pub const STANDARD_SUITE: &str = include_str!("../suites/standard.epd");
+
+/// A position and its expected perft counts, one line of a suite file
+///
+/// Lines look like "x5o/7/7/7/7/7/o5x x 0 1 ;D1 16 ;D2 256".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuiteEntry {
+    pub fen: String,
+    pub board: Board,
+    /// Depths and node counts in the order they were given
+    pub counts: Vec<(u8, u64)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuiteError {
+    Fen(FenError),
+    InvalidDepth(String),
+    InvalidCount(String),
+}
+
+impl fmt::Display for SuiteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SuiteError::Fen(e) => write!(f, "{}", e),
+            SuiteError::InvalidDepth(s) => write!(f, "invalid depth '{}', expected D<n>", s),
+            SuiteError::InvalidCount(s) => write!(f, "invalid node count '{}'", s),
+        }
+    }
+}
+
+impl Error for SuiteError {}
+
+impl From<FenError> for SuiteError {
+    fn from(e: FenError) -> Self {
+        SuiteError::Fen(e)
+    }
+}
+
+/// A `SuiteError` and the line of the suite it was on, counting from 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuiteLineError {
+    pub line: usize,
+    pub error: SuiteError,
+}
+
+impl fmt::Display for SuiteLineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+impl Error for SuiteLineError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl FromStr for SuiteEntry {
+    type Err = SuiteError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(';');
+        let fen = parts.next().unwrap_or("").trim();
+        let board = Board::try_from_fen(fen)?;
+        let mut counts = Vec::new();
+
+        for part in parts {
+            let (depth, nodes) = part
+                .trim()
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| SuiteError::InvalidCount(part.trim().to_string()))?;
+
+            let depth = depth
+                .strip_prefix('D')
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(|| SuiteError::InvalidDepth(depth.to_string()))?;
+            let nodes = nodes
+                .trim()
+                .parse()
+                .map_err(|_| SuiteError::InvalidCount(nodes.trim().to_string()))?;
+
+            counts.push((depth, nodes));
+        }
+
+        Ok(SuiteEntry {
+            fen: fen.to_string(),
+            board,
+            counts,
+        })
+    }
+}
+
+/// Parses a suite with one entry per line, blank lines and lines starting with '#' are skipped
+pub fn parse_suite(text: &str) -> Result<Vec<SuiteEntry>, SuiteLineError> {
+    text.lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| {
+            line.parse().map_err(|error| SuiteLineError {
+                line: number,
+                error,
+            })
+        })
+        .collect()
+}
+
+/// The entries of `STANDARD_SUITE`
+#[must_use]
+pub fn standard_suite() -> Vec<SuiteEntry> {
+    parse_suite(STANDARD_SUITE).expect("the standard suite is valid")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuiteFailure {
+    pub fen: String,
+    pub depth: u8,
+    pub expected: u64,
+    pub actual: u64,
+}
+
+impl fmt::Display for SuiteFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} depth {}: expected {} got {}",
+            self.fen, self.depth, self.expected, self.actual
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SuiteReport {
+    pub positions: usize,
+    /// Counts compared, not including those deeper than the maximum depth
+    pub checks: usize,
+    pub nodes: u64,
+    pub failures: Vec<SuiteFailure>,
+}
+
+impl SuiteReport {
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Checks every count up to `max_depth` with `perft`
+#[must_use]
+pub fn run_suite(entries: &[SuiteEntry], max_depth: u8) -> SuiteReport {
+    run_suite_with(entries, max_depth, perft)
+}
+
+/// Checks every count up to `max_depth` with a perft function such as `perft_parallel`
+pub fn run_suite_with<F: FnMut(&Board, u8) -> u64>(
+    entries: &[SuiteEntry],
+    max_depth: u8,
+    mut count: F,
+) -> SuiteReport {
+    let mut report = SuiteReport {
+        positions: entries.len(),
+        ..Default::default()
+    };
+
+    for entry in entries {
+        for &(depth, expected) in entry.counts.iter().filter(|(d, _)| *d <= max_depth) {
+            let actual = count(&entry.board, depth);
+            report.checks += 1;
+            report.nodes += actual;
+
+            if actual != expected {
+                report.failures.push(SuiteFailure {
+                    fen: entry.fen.clone(),
+                    depth,
+                    expected,
+                    actual,
+                });
+            }
+        }
+    }
+
+    report
+}
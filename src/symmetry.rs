@@ -0,0 +1,102 @@
+use crate::moves::Move;
+use crate::squares::Square;
+
+/// One of the 8 symmetries of the square board
+///
+/// Rotations are clockwise when the board is viewed with rank 7 at the top.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Transform {
+    Identity,
+    FlipHorizontal,
+    FlipVertical,
+    FlipDiagonal,
+    FlipAntiDiagonal,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+impl Transform {
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::FlipDiagonal,
+        Transform::FlipAntiDiagonal,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+    ];
+
+    /// The transform that undoes this one
+    #[must_use]
+    pub const fn inverse(self) -> Self {
+        match self {
+            Transform::Rotate90 => Transform::Rotate270,
+            Transform::Rotate270 => Transform::Rotate90,
+            _ => self,
+        }
+    }
+}
+
+impl Square {
+    #[must_use]
+    pub const fn transform(self, t: Transform) -> Self {
+        let x = self.file();
+        let y = self.rank();
+        let (x, y) = match t {
+            Transform::Identity => (x, y),
+            Transform::FlipHorizontal => (6 - x, y),
+            Transform::FlipVertical => (x, 6 - y),
+            Transform::FlipDiagonal => (y, x),
+            Transform::FlipAntiDiagonal => (6 - y, 6 - x),
+            Transform::Rotate90 => (y, 6 - x),
+            Transform::Rotate180 => (6 - x, 6 - y),
+            Transform::Rotate270 => (6 - y, x),
+        };
+        Square::ALL[(7 * y + x) as usize]
+    }
+}
+
+impl Move {
+    #[must_use]
+    pub const fn transform(&self, t: Transform) -> Self {
+        match self {
+            Move::Pass => Move::Pass,
+            Move::Drop(sq) => Move::Drop(sq.transform(t)),
+            Move::Jump(fr, to) => Move::Jump(fr.transform(t), to.transform(t)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Square, Transform};
+
+    #[test]
+    fn square() {
+        let tests = [
+            (Transform::Identity, Square::B1),
+            (Transform::FlipHorizontal, Square::F1),
+            (Transform::FlipVertical, Square::B7),
+            (Transform::FlipDiagonal, Square::A2),
+            (Transform::FlipAntiDiagonal, Square::G6),
+            (Transform::Rotate90, Square::A6),
+            (Transform::Rotate180, Square::F7),
+            (Transform::Rotate270, Square::G2),
+        ];
+
+        for (t, sq) in tests {
+            assert_eq!(Square::B1.transform(t), sq);
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        for t in Transform::ALL {
+            for sq in Square::ALL {
+                assert_eq!(sq.transform(t).transform(t.inverse()), sq);
+            }
+        }
+    }
+}
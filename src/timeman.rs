@@ -0,0 +1,252 @@
+use crate::board::Board;
+use crate::colour::Colour;
+use crate::moves::Move;
+use std::time::{Duration, Instant};
+
+/// Time kept back for sending the move to the GUI
+pub const MOVE_OVERHEAD: Duration = Duration::from_millis(30);
+
+/// Moves left in the game assumed when the GUI does not say
+const DEFAULT_MOVESTOGO: u32 = 30;
+
+/// Source of elapsed time, so tests can control it
+pub trait Clock {
+    /// Time since the clock started
+    fn elapsed(&self) -> Duration;
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct SystemClock(Instant);
+
+impl SystemClock {
+    #[must_use]
+    pub fn new() -> Self {
+        SystemClock(Instant::now())
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn elapsed(&self) -> Duration {
+        self.0.elapsed()
+    }
+}
+
+/// Remaining time and increment of both players, as given by "go wtime ... btime ..."
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Clocks {
+    pub btime: Duration,
+    pub wtime: Duration,
+    pub binc: Duration,
+    pub winc: Duration,
+    pub movestogo: Option<u32>,
+}
+
+/// Decides how long to search from the clock of the side to move
+///
+/// The soft limit is checked between iterations and grows when the best move keeps
+/// changing, the hard limit is never exceeded.
+#[derive(Debug)]
+pub struct TimeManager<C: Clock = SystemClock> {
+    clock: C,
+    soft: Duration,
+    hard: Duration,
+    best_move: Option<Move>,
+    stability: u32,
+}
+
+impl<C: Clock> TimeManager<C> {
+    #[must_use]
+    pub fn new(clock: C, clocks: &Clocks, pos: &Board) -> Self {
+        let (time, inc) = match pos.turn() {
+            Colour::Black => (clocks.btime, clocks.binc),
+            Colour::White => (clocks.wtime, clocks.winc),
+        };
+        let time = time.saturating_sub(MOVE_OVERHEAD);
+        let movestogo = clocks.movestogo.unwrap_or(DEFAULT_MOVESTOGO).max(1);
+
+        let base = time / movestogo + inc * 3 / 4;
+        let hard = (base * 4).min(time * 3 / 4);
+
+        TimeManager {
+            clock,
+            soft: base.min(hard),
+            hard,
+            best_move: None,
+            stability: 0,
+        }
+    }
+
+    #[must_use]
+    pub const fn soft_limit(&self) -> Duration {
+        self.soft
+    }
+
+    #[must_use]
+    pub const fn hard_limit(&self) -> Duration {
+        self.hard
+    }
+
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.clock.elapsed()
+    }
+
+    /// Records the best move of a completed iteration
+    pub fn update(&mut self, best_move: Option<Move>) {
+        if best_move == self.best_move {
+            self.stability += 1;
+        } else {
+            self.best_move = best_move;
+            self.stability = 0;
+        }
+    }
+
+    /// The soft limit scaled by how long the best move has stayed the same
+    #[must_use]
+    pub fn optimum(&self) -> Duration {
+        let percent = match self.stability {
+            0 => 150,
+            1 => 120,
+            2 => 100,
+            3 => 80,
+            _ => 60,
+        };
+        (self.soft * percent / 100).min(self.hard)
+    }
+
+    /// Whether to start another iteration
+    #[must_use]
+    pub fn should_continue(&self) -> bool {
+        self.elapsed() < self.optimum()
+    }
+
+    /// Whether to abort the current iteration
+    #[must_use]
+    pub fn should_stop(&self) -> bool {
+        self.elapsed() >= self.hard
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::squares::Square;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[derive(Clone, Default)]
+    struct MockClock(Rc<Cell<Duration>>);
+
+    impl MockClock {
+        fn advance(&self, millis: u64) {
+            self.0.set(self.0.get() + Duration::from_millis(millis));
+        }
+    }
+
+    impl Clock for MockClock {
+        fn elapsed(&self) -> Duration {
+            self.0.get()
+        }
+    }
+
+    fn clocks(btime: u64, wtime: u64, inc: u64, movestogo: Option<u32>) -> Clocks {
+        Clocks {
+            btime: Duration::from_millis(btime),
+            wtime: Duration::from_millis(wtime),
+            binc: Duration::from_millis(inc),
+            winc: Duration::from_millis(inc),
+            movestogo,
+        }
+    }
+
+    #[test]
+    fn side_to_move() {
+        let clocks = clocks(60_030, 3_030, 0, None);
+        let black = TimeManager::new(MockClock::default(), &clocks, &Board::from_fen("startpos"));
+        let white = TimeManager::new(
+            MockClock::default(),
+            &clocks,
+            &Board::from_fen("x5o/7/7/7/7/7/o5x o 0 1"),
+        );
+
+        assert_eq!(black.soft_limit(), Duration::from_millis(2000));
+        assert_eq!(black.hard_limit(), Duration::from_millis(8000));
+        assert_eq!(white.soft_limit(), Duration::from_millis(100));
+        assert_eq!(white.hard_limit(), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn limits() {
+        let pos = Board::from_fen("startpos");
+
+        // Increment
+        let tm = TimeManager::new(MockClock::default(), &clocks(30_030, 0, 1000, None), &pos);
+        assert_eq!(tm.soft_limit(), Duration::from_millis(1750));
+
+        // Moves to go
+        let tm = TimeManager::new(MockClock::default(), &clocks(10_030, 0, 0, Some(5)), &pos);
+        assert_eq!(tm.soft_limit(), Duration::from_millis(2000));
+        assert_eq!(tm.hard_limit(), Duration::from_millis(7500));
+
+        // The last move before the time control never uses everything
+        let tm = TimeManager::new(MockClock::default(), &clocks(10_030, 0, 0, Some(1)), &pos);
+        assert_eq!(tm.soft_limit(), Duration::from_millis(7500));
+        assert_eq!(tm.hard_limit(), Duration::from_millis(7500));
+
+        // Less time than the overhead
+        let tm = TimeManager::new(MockClock::default(), &clocks(10, 0, 0, None), &pos);
+        assert_eq!(tm.hard_limit(), Duration::ZERO);
+        assert!(tm.should_stop());
+    }
+
+    #[test]
+    fn hard_limit() {
+        let clock = MockClock::default();
+        let tm = TimeManager::new(
+            clock.clone(),
+            &clocks(60_030, 0, 0, None),
+            &Board::from_fen("startpos"),
+        );
+
+        clock.advance(7999);
+        assert!(!tm.should_stop());
+        clock.advance(1);
+        assert!(tm.should_stop());
+        assert!(!tm.should_continue());
+    }
+
+    #[test]
+    fn stability() {
+        let clock = MockClock::default();
+        let mut tm = TimeManager::new(
+            clock.clone(),
+            &clocks(60_030, 0, 0, None),
+            &Board::from_fen("startpos"),
+        );
+        let a = Some(Move::Drop(Square::A2));
+        let b = Some(Move::Drop(Square::B2));
+
+        // A changing best move extends the search past the soft limit
+        tm.update(a);
+        tm.update(b);
+        assert_eq!(tm.optimum(), Duration::from_millis(3000));
+        clock.advance(2500);
+        assert!(tm.should_continue());
+
+        // A stable best move cuts it short
+        for _ in 0..4 {
+            tm.update(b);
+        }
+        assert_eq!(tm.optimum(), Duration::from_millis(1200));
+        assert!(!tm.should_continue());
+
+        tm.update(a);
+        assert!(tm.should_continue());
+    }
+}
@@ -0,0 +1,352 @@
+use crate::moves::Move;
+use crate::squares::Square;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+
+/// Size used by `search` when no table is given
+pub const DEFAULT_HASH_MB: usize = 16;
+
+const BUCKET_SIZE: usize = 4;
+const AGE_MASK: u8 = 0x3f;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TTEntry {
+    pub depth: u8,
+    pub score: i32,
+    pub bound: Bound,
+    pub mv: Option<Move>,
+}
+
+/// Entries are stored as the key XOR the data next to the data itself, so a torn
+/// write from another thread fails the key check instead of returning garbage.
+#[derive(Debug, Default)]
+struct Slot {
+    key: AtomicU64,
+    data: AtomicU64,
+}
+
+/// Shared hash table of search results, sized in megabytes
+///
+/// Every method except `clear` and `resize` takes `&self` so the table can be
+/// shared between threads without a lock.
+#[derive(Debug)]
+pub struct TranspositionTable {
+    slots: Vec<Slot>,
+    age: AtomicU8,
+}
+
+// Layout of the data word:
+// bits  0-15  score
+// bits 16-23  depth
+// bits 24-25  bound, 0 means the slot is empty
+// bits 26-31  age
+// bits 32-45  move
+fn pack(entry: &TTEntry, age: u8) -> u64 {
+    let bound = match entry.bound {
+        Bound::Exact => 1,
+        Bound::Lower => 2,
+        Bound::Upper => 3,
+    };
+
+    (entry.score as i16 as u16 as u64)
+        | (entry.depth as u64) << 16
+        | bound << 24
+        | ((age & AGE_MASK) as u64) << 26
+        | (pack_move(entry.mv) as u64) << 32
+}
+
+fn unpack(data: u64) -> Option<TTEntry> {
+    let bound = match (data >> 24) & 3 {
+        1 => Bound::Exact,
+        2 => Bound::Lower,
+        3 => Bound::Upper,
+        _ => return None,
+    };
+
+    Some(TTEntry {
+        depth: (data >> 16) as u8,
+        score: data as u16 as i16 as i32,
+        bound,
+        mv: unpack_move((data >> 32) as u16),
+    })
+}
+
+const fn data_age(data: u64) -> u8 {
+    (data >> 26) as u8 & AGE_MASK
+}
+
+// 2 bits of move type followed by two 6 bit squares
+fn pack_move(mv: Option<Move>) -> u16 {
+    match mv {
+        None => 0,
+        Some(Move::Pass) => 1,
+        Some(Move::Drop(sq)) => 2 | (sq as u16) << 8,
+        Some(Move::Jump(fr, to)) => 3 | (fr as u16) << 2 | (to as u16) << 8,
+    }
+}
+
+fn unpack_move(data: u16) -> Option<Move> {
+    let fr = Square::try_from(((data >> 2) & 0x3f) as u8).ok()?;
+    let to = Square::try_from(((data >> 8) & 0x3f) as u8).ok()?;
+
+    match data & 3 {
+        1 => Some(Move::Pass),
+        2 => Some(Move::Drop(to)),
+        3 => Some(Move::Jump(fr, to)),
+        _ => None,
+    }
+}
+
+/// As many whole buckets as fit in `mb` megabytes, at least one
+fn allocate(mb: usize, bucket_size: usize) -> Vec<Slot> {
+    let buckets = (mb * 1024 * 1024 / (bucket_size * std::mem::size_of::<Slot>())).max(1);
+    let mut slots = Vec::new();
+    slots.resize_with(buckets * bucket_size, Slot::default);
+    slots
+}
+
+fn bucket(slots: &[Slot], key: u64, bucket_size: usize) -> &[Slot] {
+    let buckets = slots.len() / bucket_size;
+    let idx = ((key as u128 * buckets as u128) >> 64) as usize;
+    &slots[idx * bucket_size..(idx + 1) * bucket_size]
+}
+
+impl TranspositionTable {
+    #[must_use]
+    pub fn new(mb: usize) -> Self {
+        let mut tt = TranspositionTable {
+            slots: Vec::new(),
+            age: AtomicU8::new(0),
+        };
+        tt.resize(mb);
+        tt
+    }
+
+    /// Replaces the table with an empty one of the new size, always at least one bucket
+    pub fn resize(&mut self, mb: usize) {
+        // Free the old table first so that both are never allocated at once
+        self.slots = Vec::new();
+        self.slots = allocate(mb, BUCKET_SIZE);
+        self.age.store(0, Ordering::Relaxed);
+    }
+
+    pub fn clear(&mut self) {
+        for slot in &mut self.slots {
+            *slot.key.get_mut() = 0;
+            *slot.data.get_mut() = 0;
+        }
+        self.age.store(0, Ordering::Relaxed);
+    }
+
+    /// Number of entries the table can hold
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Marks existing entries as older, call once before each search
+    pub fn new_search(&self) {
+        let age = self.age.load(Ordering::Relaxed);
+        self.age.store((age + 1) & AGE_MASK, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn probe(&self, key: u64) -> Option<TTEntry> {
+        for slot in self.bucket(key) {
+            let data = slot.data.load(Ordering::Relaxed);
+            if slot.key.load(Ordering::Relaxed) ^ data == key {
+                return unpack(data);
+            }
+        }
+
+        None
+    }
+
+    /// Stores over an entry for the same key, or else the shallowest and oldest in the bucket
+    pub fn store(&self, key: u64, entry: TTEntry) {
+        let age = self.age.load(Ordering::Relaxed);
+        let bucket = self.bucket(key);
+        let mut replace = &bucket[0];
+        let mut worst = i32::MAX;
+
+        for slot in bucket {
+            let data = slot.data.load(Ordering::Relaxed);
+
+            if slot.key.load(Ordering::Relaxed) ^ data == key {
+                // Keep the old move rather than losing it to an entry without one
+                let mut entry = entry;
+                if entry.mv.is_none() {
+                    entry.mv = unpack(data).and_then(|old| old.mv);
+                }
+                let new = pack(&entry, age);
+                slot.key.store(key ^ new, Ordering::Relaxed);
+                slot.data.store(new, Ordering::Relaxed);
+                return;
+            }
+
+            let relative_age = (age.wrapping_sub(data_age(data)) & AGE_MASK) as i32;
+            let value = match unpack(data) {
+                Some(old) => old.depth as i32 - 8 * relative_age,
+                None => i32::MIN,
+            };
+
+            if value < worst {
+                worst = value;
+                replace = slot;
+            }
+        }
+
+        let new = pack(&entry, age);
+        replace.key.store(key ^ new, Ordering::Relaxed);
+        replace.data.store(new, Ordering::Relaxed);
+    }
+
+    /// Per mille of sampled entries written during the current search
+    #[must_use]
+    pub fn hashfull(&self) -> usize {
+        let age = self.age.load(Ordering::Relaxed);
+        let sample = self.slots.len().min(1000);
+        let used = self.slots[..sample]
+            .iter()
+            .map(|slot| slot.data.load(Ordering::Relaxed))
+            .filter(|&data| unpack(data).is_some() && data_age(data) == age)
+            .count();
+
+        used * 1000 / sample
+    }
+
+    fn bucket(&self, key: u64) -> &[Slot] {
+        bucket(&self.slots, key, BUCKET_SIZE)
+    }
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_HASH_MB)
+    }
+}
+
+/// `PerftTable` counts share their slot with an 8 bit depth
+const PERFT_MAX_NODES: u64 = 1 << 56;
+
+/// Node counts for `perft_hashed`, sized in megabytes
+///
+/// Each bucket has one slot that keeps the deepest count and one that is always
+/// replaced. Counts are stored with their depth in the low 8 bits, so only counts
+/// below 2^56 are kept.
+#[derive(Debug)]
+pub struct PerftTable {
+    slots: Vec<Slot>,
+}
+
+impl PerftTable {
+    #[must_use]
+    pub fn new(mb: usize) -> Self {
+        PerftTable {
+            slots: allocate(mb, 2),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for slot in &mut self.slots {
+            *slot.key.get_mut() = 0;
+            *slot.data.get_mut() = 0;
+        }
+    }
+
+    /// Number of counts the table can hold
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    #[must_use]
+    pub fn probe(&self, key: u64, depth: u8) -> Option<u64> {
+        for slot in bucket(&self.slots, key, 2) {
+            let data = slot.data.load(Ordering::Relaxed);
+            if slot.key.load(Ordering::Relaxed) ^ data == key && data as u8 == depth {
+                return Some(data >> 8);
+            }
+        }
+
+        None
+    }
+
+    /// Counts too large to pack with the depth are not stored
+    pub fn store(&self, key: u64, depth: u8, nodes: u64) {
+        if nodes >= PERFT_MAX_NODES {
+            return;
+        }
+
+        let slots = bucket(&self.slots, key, 2);
+        let data = nodes << 8 | depth as u64;
+        let deepest = slots[0].data.load(Ordering::Relaxed) as u8;
+        let slot = if depth >= deepest {
+            &slots[0]
+        } else {
+            &slots[1]
+        };
+
+        slot.key.store(key ^ data, Ordering::Relaxed);
+        slot.data.store(data, Ordering::Relaxed);
+    }
+}
+
+impl Default for PerftTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_HASH_MB)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perft_large_counts() {
+        let table = PerftTable::new(1);
+        table.store(1, 9, PERFT_MAX_NODES - 1);
+        assert_eq!(table.probe(1, 9), Some(PERFT_MAX_NODES - 1));
+
+        table.store(2, 9, PERFT_MAX_NODES);
+        assert_eq!(table.probe(2, 9), None);
+    }
+
+    #[test]
+    fn pack_round_trip() {
+        let moves = [
+            None,
+            Some(Move::Pass),
+            Some(Move::Drop(Square::A1)),
+            Some(Move::Drop(Square::G7)),
+            Some(Move::Jump(Square::A1, Square::C3)),
+            Some(Move::Jump(Square::G7, Square::E5)),
+        ];
+
+        for mv in moves {
+            for (score, bound) in [
+                (0, Bound::Exact),
+                (-29_990, Bound::Lower),
+                (31_000, Bound::Upper),
+            ] {
+                let entry = TTEntry {
+                    depth: 17,
+                    score,
+                    bound,
+                    mv,
+                };
+                let data = pack(&entry, 63);
+                assert_eq!(unpack(data), Some(entry));
+                assert_eq!(data_age(data), 63);
+            }
+        }
+
+        assert_eq!(unpack(0), None);
+    }
+}
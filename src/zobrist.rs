@@ -0,0 +1,41 @@
+const fn splitmix64(state: u64) -> u64 {
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+const fn generate_keys() -> [[u64; 49]; 3] {
+    let mut keys = [[0u64; 49]; 3];
+    let mut state: u64 = 0x1a2b3c4d5e6f7081;
+    let mut i = 0;
+    while i < 3 {
+        let mut sq = 0;
+        while sq < 49 {
+            state = state.wrapping_add(0x9e3779b97f4a7c15);
+            keys[i][sq] = splitmix64(state);
+            sq += 1;
+        }
+        i += 1;
+    }
+    keys
+}
+
+const fn generate_flips(keys: &[[u64; 49]; 3]) -> [u64; 49] {
+    let mut flips = [0u64; 49];
+    let mut sq = 0;
+    while sq < 49 {
+        flips[sq] = keys[0][sq] ^ keys[1][sq];
+        sq += 1;
+    }
+    flips
+}
+
+/// Keys for black, white and blocker pieces, indexed the same way as `Board::pieces`
+pub const ZOBRIST_PIECES: [[u64; 49]; 3] = generate_keys();
+
+/// Key toggled when a piece on a square changes colour
+pub const ZOBRIST_FLIP: [u64; 49] = generate_flips(&ZOBRIST_PIECES);
+
+/// Key present when white is to move
+pub const ZOBRIST_TURN: u64 = splitmix64(0x0f1e2d3c4b5a6978);
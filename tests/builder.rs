@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use ataxx::{Bitboard, Board, BoardBuilder, BoardError, Colour, Square};
+
+    #[test]
+    fn build() {
+        let pos = BoardBuilder::new()
+            .black(Square::A7)
+            .black(Square::G1)
+            .white(Square::A1)
+            .white(Square::G7)
+            .build()
+            .unwrap();
+        assert_eq!(pos, Board::from_fen("startpos"));
+
+        let pos = BoardBuilder::new()
+            .piece(Colour::Black, Square::A7)
+            .piece(Colour::White, Square::G1)
+            .blocker(Square::D4)
+            .turn(Colour::White)
+            .halfmoves(20)
+            .fullmoves(40)
+            .build()
+            .unwrap();
+        assert_eq!(pos.get_fen(), "x6/7/7/3-3/7/7/6o o 20 40");
+        assert_eq!(pos.hash(), pos.calculate_hash());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            BoardBuilder::new()
+                .black(Square::A1)
+                .white(Square::A1)
+                .build(),
+            Err(BoardError::Overlap(Square::A1))
+        );
+        assert_eq!(
+            BoardBuilder::new()
+                .blocker(Square::C3)
+                .blocker(Square::C3)
+                .build(),
+            Err(BoardError::Overlap(Square::C3))
+        );
+        assert_eq!(
+            BoardBuilder::new()
+                .pieces([Bitboard(1 << 49), Bitboard(0), Bitboard(0)])
+                .build(),
+            Err(BoardError::OutsideBoard(Bitboard(1 << 49)))
+        );
+        assert_eq!(
+            BoardBuilder::new().halfmoves(101).build(),
+            Err(BoardError::InvalidHalfmoves(101))
+        );
+        assert_eq!(
+            BoardBuilder::new().fullmoves(0).build(),
+            Err(BoardError::InvalidFullmoves(0))
+        );
+    }
+
+    #[test]
+    fn is_valid() {
+        let tests: [(Board, bool); 6] = [
+            (Board::from_fen("startpos"), true),
+            (Board::from_fen("x5o/7/7/7/7/7/o5x x 100 1"), true),
+            (
+                Board::new([Bitboard(1), Bitboard(1), Bitboard(0)], Colour::Black, 0, 1),
+                false,
+            ),
+            (
+                Board::new(
+                    [Bitboard(0), Bitboard(1 << 63), Bitboard(0)],
+                    Colour::Black,
+                    0,
+                    1,
+                ),
+                false,
+            ),
+            (
+                Board::new([Bitboard(1), Bitboard(2), Bitboard(4)], Colour::Black, 0, 0),
+                false,
+            ),
+            (
+                Board::new(
+                    [Bitboard(1), Bitboard(2), Bitboard(4)],
+                    Colour::Black,
+                    101,
+                    1,
+                ),
+                false,
+            ),
+        ];
+
+        for (pos, valid) in tests {
+            assert_eq!(pos.is_valid(), valid);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use ataxx::{Board, Evaluator, HandcraftedEval, Transform, Weights};
+
+    const FENS: [&str; 5] = [
+        "x5o/7/7/7/7/7/o5x x 0 1",
+        "x5o/7/2-1-2/7/2-1-2/7/o5x o 0 1",
+        "7/7/7/2x1o2/7/7/7 x 0 1",
+        "x5o/6-/1-4-/-3--1/2-4/7/o-3-x x 0 1",
+        "xxo4/xoo4/1x5/7/3-3/6o/o5x o 12 30",
+    ];
+
+    #[test]
+    fn startpos() {
+        let pos = Board::from_fen("startpos");
+        assert_eq!(HandcraftedEval::default().eval(&pos), 0);
+    }
+
+    #[test]
+    fn side_relative() {
+        let eval = HandcraftedEval::default();
+
+        for fen in FENS {
+            let pos = Board::from_fen(fen);
+            let flipped = Board::new(pos.pieces(), !pos.turn(), pos.halfmoves(), pos.fullmoves());
+            assert_eq!(eval.eval(&pos), -eval.eval(&flipped));
+            assert_eq!(eval.eval(&pos), eval.eval(&pos.swap_colours()));
+        }
+    }
+
+    #[test]
+    fn symmetry() {
+        let eval = HandcraftedEval::default();
+
+        for fen in FENS {
+            let pos = Board::from_fen(fen);
+            for t in Transform::ALL {
+                assert_eq!(eval.eval(&pos), eval.eval(&pos.transform(t)));
+            }
+        }
+    }
+
+    #[test]
+    fn material_only() {
+        let eval = HandcraftedEval::new(Weights {
+            material: 1,
+            mobility: 0,
+            frontier: 0,
+            edge: 0,
+            corner: 0,
+        });
+
+        for fen in FENS {
+            let pos = Board::from_fen(fen);
+            assert_eq!(eval.eval(&pos), pos.material());
+        }
+    }
+
+    #[test]
+    fn explain() {
+        let eval = HandcraftedEval::default();
+        let pos = Board::from_fen("xxo4/xoo4/1x5/7/3-3/6o/o5x o 12 30");
+        let explanation = eval.explain(&pos);
+
+        assert_eq!(explanation.total(), eval.eval(&pos));
+        assert_eq!(explanation.material.value, pos.material());
+        assert_eq!(explanation.corner.value, -1);
+        assert!(explanation.mobility.value > 0);
+
+        let text = explanation.to_string();
+        for name in [
+            "material", "mobility", "frontier", "edge", "corner", "total",
+        ] {
+            assert!(text.contains(name));
+        }
+    }
+}
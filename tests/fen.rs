@@ -18,4 +18,73 @@ mod tests {
             assert_eq!(pos.get_fen(), fen);
         }
     }
+
+    #[test]
+    fn fen_errors() {
+        use ataxx::FenError;
+
+        let tests = [
+            ("x5o/7/7/7/7/7/o5x x 0", FenError::WrongFieldCount(3)),
+            ("x5o/7/7/7/7/7/o5x x 0 1 1", FenError::WrongFieldCount(5)),
+            (
+                "x5o/7/7/7/7/7/o5z x 0 1",
+                FenError::InvalidPiece {
+                    rank: 1,
+                    token: 'z',
+                },
+            ),
+            (
+                "x5oo/7/7/7/7/7/o5x x 0 1",
+                FenError::RankTooLong { rank: 7 },
+            ),
+            (
+                "x5o/7/8/7/7/7/o5x x 0 1",
+                FenError::InvalidPiece {
+                    rank: 5,
+                    token: '8',
+                },
+            ),
+            (
+                "x5o/7/4/7/7/7/o5x x 0 1",
+                FenError::RankTooShort { rank: 5 },
+            ),
+            (
+                "x5o/7/7/44/7/7/o5x x 0 1",
+                FenError::RankTooLong { rank: 4 },
+            ),
+            ("x5o/7/7/7/7/7/o5x/ x 0 1", FenError::TooManyRanks),
+            ("x5o/7/7/7/7/o5x x 0 1", FenError::TooFewRanks(6)),
+            (
+                "x5o/7/7/7/7/7/o5x b 0 1",
+                FenError::InvalidTurn("b".to_string()),
+            ),
+            (
+                "x5o/7/7/7/7/7/o5x x -1 1",
+                FenError::InvalidHalfmoves("-1".to_string()),
+            ),
+            (
+                "x5o/7/7/7/7/7/o5x x 99999999999 1",
+                FenError::InvalidHalfmoves("99999999999".to_string()),
+            ),
+            (
+                "x5o/7/7/7/7/7/o5x x 0 a",
+                FenError::InvalidFullmoves("a".to_string()),
+            ),
+            (
+                "x5o/7/7/7/7/7/o5x x 0 0",
+                FenError::InvalidFullmoves("0".to_string()),
+            ),
+        ];
+
+        for (fen, expected) in tests {
+            assert_eq!(ataxx::Board::try_from_fen(fen), Err(expected));
+        }
+    }
+
+    #[test]
+    fn fen_from_str() {
+        let pos: ataxx::Board = "startpos".parse().unwrap();
+        assert_eq!(pos.get_fen(), "x5o/7/7/7/7/7/o5x x 0 1");
+        assert!("x5o/7/7/7/7/7/o5x".parse::<ataxx::Board>().is_err());
+    }
 }
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use ataxx::{Board, Game, GameError, Move, MoveParseError, Square};
+
+    #[test]
+    fn play_undo_redo() {
+        let mut game: Game = "startpos moves f1 a1c3".parse().unwrap();
+        let after = *game.board();
+        assert_eq!(game.moves().len(), 2);
+        assert_eq!(game.keys().len(), 3);
+
+        assert_eq!(game.undo(), Some(Move::Jump(Square::A1, Square::C3)));
+        assert_eq!(game.undo(), Some(Move::Drop(Square::F1)));
+        assert_eq!(game.undo(), None);
+        assert_eq!(game.board(), &Board::from_fen("startpos"));
+        assert_eq!(game.keys().len(), 1);
+
+        assert_eq!(game.redo(), Some(Move::Drop(Square::F1)));
+        assert_eq!(game.redo(), Some(Move::Jump(Square::A1, Square::C3)));
+        assert_eq!(game.redo(), None);
+        assert_eq!(game.board(), &after);
+
+        game.undo();
+        game.play(Move::Drop(Square::B1)).unwrap();
+        assert_eq!(game.redo(), None);
+    }
+
+    #[test]
+    fn illegal_moves() {
+        let mut game = Game::new(Board::from_fen("startpos"));
+        assert_eq!(
+            game.play(Move::Drop(Square::D4)),
+            Err(GameError::IllegalMove(Move::Drop(Square::D4)))
+        );
+        assert_eq!(
+            game.play(Move::Pass),
+            Err(GameError::IllegalMove(Move::Pass))
+        );
+        assert_eq!(
+            game.play_str("d4"),
+            Err(GameError::InvalidMove(MoveParseError::Illegal(Move::Drop(
+                Square::D4
+            ))))
+        );
+        assert!(game.moves().is_empty());
+
+        assert!("startpos moves a1a2 d4".parse::<Game>().is_err());
+        assert!("position startpos".parse::<Game>().is_err());
+        assert!("fen x5o/7/7/7/7/7/o5x x 0".parse::<Game>().is_err());
+    }
+
+    #[test]
+    fn positions() {
+        let game: Game = "fen x5o/7/7/7/7/7/o5x o 0 1 moves b2 f2 b2b4"
+            .parse()
+            .unwrap();
+        let positions: Vec<Board> = game.positions().collect();
+
+        assert_eq!(positions.len(), 4);
+        assert_eq!(positions[0], *game.start());
+        assert_eq!(positions[3], *game.board());
+        for (pos, key) in positions.iter().zip(game.keys()) {
+            assert_eq!(pos.hash(), *key);
+        }
+    }
+
+    #[test]
+    fn threefold() {
+        let mut game = Game::new(Board::from_fen("startpos"));
+
+        for _ in 0..2 {
+            assert!(!game.is_repetition());
+            for mv in ["g1e1", "a1c1", "e1g1", "c1a1"] {
+                game.play_str(mv).unwrap();
+            }
+        }
+
+        assert_eq!(game.repetitions(), 3);
+        assert!(game.is_repetition());
+        assert!(game.is_gameover());
+        assert!(!game.board().is_gameover());
+        assert!(game.play_str("g1e1").is_err());
+
+        game.undo();
+        assert!(!game.is_repetition());
+    }
+
+    #[test]
+    fn replay_past_repetition() {
+        let mut game = Game::new(Board::from_fen("startpos"));
+        for mv in [
+            "g1e1", "a1c1", "e1g1", "c1a1", "g1e1", "a1c1", "e1g1", "c1a1",
+        ] {
+            game.play_str(mv).unwrap();
+        }
+
+        assert!(game.is_repetition());
+        assert!(game.play_str("g1e1").is_err());
+        game.replay_str("g1e1").unwrap();
+        assert!(game.replay_str("a1a1").is_err());
+        assert_eq!(game.moves().len(), 9);
+
+        let game: Game = "startpos moves g1e1 a1c1 e1g1 c1a1 g1e1 a1c1 e1g1 c1a1 g1e1"
+            .parse()
+            .unwrap();
+        assert_eq!(game.moves().len(), 9);
+    }
+
+    #[test]
+    fn single_moves_reset_repetitions() {
+        let mut game = Game::new(Board::from_fen("startpos"));
+
+        for mv in ["g1e1", "a1c1", "e1g1", "c1a1", "g1e1", "a1c1"] {
+            game.play_str(mv).unwrap();
+        }
+        game.play_str("e2").unwrap();
+        assert_eq!(game.repetitions(), 1);
+    }
+
+    #[test]
+    fn repetition_result() {
+        use ataxx::{GameResult, Outcome, Termination};
+
+        let game: Game = "startpos moves g1e1 a1c1 e1g1 c1a1 g1e1 a1c1 e1g1 c1a1"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            game.result(),
+            Some(GameResult::new(Outcome::Draw, Termination::Repetition))
+        );
+        assert_eq!(game.board().result(), None);
+    }
+}
@@ -1,23 +1,56 @@
 #[cfg(test)]
 mod tests {
+    use ataxx::{GameResult, Outcome, Termination};
+
     #[test]
     fn gameover_true() {
-        let tests: [&str; 10] = [
-            "7/7/7/7/7/7/7 x 0 1",
-            "7/7/7/7/7/7/7 o 0 1",
-            "7/7/7/7/7/7/x6 x 0 1",
-            "7/7/7/7/7/7/x6 o 0 1",
-            "7/7/7/7/7/7/o6 x 0 1",
-            "7/7/7/7/7/7/o6 o 0 1",
-            "x5o/7/7/7/7/7/o5x x 100 1",
-            "x5o/7/7/7/7/7/o5x o 100 1",
-            "7/7/7/7/-------/-------/ooooxxx x 0 1",
-            "7/7/7/7/-------/-------/ooooxxx o 0 1",
+        let tests: [(&str, GameResult); 10] = [
+            (
+                "7/7/7/7/7/7/7 x 0 1",
+                GameResult::new(Outcome::Draw, Termination::NoPieces),
+            ),
+            (
+                "7/7/7/7/7/7/7 o 0 1",
+                GameResult::new(Outcome::Draw, Termination::NoPieces),
+            ),
+            (
+                "7/7/7/7/7/7/x6 x 0 1",
+                GameResult::new(Outcome::BlackWin, Termination::NoPieces),
+            ),
+            (
+                "7/7/7/7/7/7/x6 o 0 1",
+                GameResult::new(Outcome::BlackWin, Termination::NoPieces),
+            ),
+            (
+                "7/7/7/7/7/7/o6 x 0 1",
+                GameResult::new(Outcome::WhiteWin, Termination::NoPieces),
+            ),
+            (
+                "7/7/7/7/7/7/o6 o 0 1",
+                GameResult::new(Outcome::WhiteWin, Termination::NoPieces),
+            ),
+            (
+                "x5o/7/7/7/7/7/o5x x 100 1",
+                GameResult::new(Outcome::Draw, Termination::HalfmoveLimit),
+            ),
+            (
+                "x5o/7/7/7/7/7/o5x o 100 1",
+                GameResult::new(Outcome::Draw, Termination::HalfmoveLimit),
+            ),
+            (
+                "7/7/7/7/-------/-------/ooooxxx x 0 1",
+                GameResult::new(Outcome::WhiteWin, Termination::NoMoves),
+            ),
+            (
+                "7/7/7/7/-------/-------/ooooxxx o 0 1",
+                GameResult::new(Outcome::WhiteWin, Termination::NoMoves),
+            ),
         ];
 
-        for fen in tests {
+        for (fen, result) in tests {
             let pos = ataxx::Board::from_fen(fen);
             assert!(pos.is_gameover());
+            assert_eq!(pos.result(), Some(result));
         }
     }
 
@@ -39,6 +72,47 @@ mod tests {
         for fen in tests {
             let pos = ataxx::Board::from_fen(fen);
             assert!(!pos.is_gameover());
+            assert_eq!(pos.result(), None);
+        }
+    }
+
+    #[test]
+    fn full_board() {
+        let tests: [(&str, Outcome); 3] = [
+            (
+                "xxxxxxx/xxxxxxx/xxxxxxx/xxxoooo/ooooooo/ooooooo/ooooooo x 0 1",
+                Outcome::WhiteWin,
+            ),
+            (
+                "xxxxxxx/xxxxxxx/xxxxxxx/xxxxxoo/ooooooo/ooooooo/ooooooo o 0 1",
+                Outcome::BlackWin,
+            ),
+            (
+                "xxxxxxx/xxxxxxx/xxxxxxx/xxx-ooo/ooooooo/ooooooo/ooooooo x 0 1",
+                Outcome::Draw,
+            ),
+        ];
+
+        for (fen, outcome) in tests {
+            let pos = ataxx::Board::from_fen(fen);
+            assert_eq!(
+                pos.result(),
+                Some(GameResult::new(outcome, Termination::NoMoves))
+            );
+        }
+    }
+
+    #[test]
+    fn material() {
+        let tests: [(&str, i32); 4] = [
+            ("x5o/7/7/7/7/7/o5x x 0 1", 0),
+            ("7/7/7/7/-------/-------/ooooxxx x 0 1", -1),
+            ("7/7/7/7/-------/-------/ooooxxx o 0 1", 1),
+            ("7/7/7/7/7/7/x6 o 0 1", -1),
+        ];
+
+        for (fen, score) in tests {
+            assert_eq!(ataxx::Board::from_fen(fen).material(), score);
         }
     }
 }
@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use ataxx::Rng;
+
+    #[test]
+    fn hash_random_games() {
+        let fens = [
+            "startpos",
+            "x5o/7/2-1-2/7/2-1-2/7/o5x x 0 1",
+            "x5o/7/3-3/2-1-2/3-3/7/o5x o 0 1",
+            "7/7/7/7/-------/-------/x5o x 0 1",
+        ];
+        let mut rng = Rng::new(0x2545f4914f6cdd1d);
+
+        for fen in fens {
+            for _ in 0..50 {
+                let mut pos = ataxx::Board::from_fen(fen);
+                assert_eq!(pos.hash(), pos.calculate_hash());
+
+                while !pos.is_gameover() {
+                    let moves = pos.legal_moves();
+                    let mv = moves[rng.below(moves.len())];
+                    pos.makemove(&mv);
+                    assert_eq!(pos.hash(), pos.calculate_hash());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hash_side_to_move() {
+        let black = ataxx::Board::from_fen("x5o/7/7/7/7/7/o5x x 0 1");
+        let white = ataxx::Board::from_fen("x5o/7/7/7/7/7/o5x o 0 1");
+        assert_ne!(black.hash(), white.hash());
+        assert_eq!(black.after_pass().hash(), white.hash());
+    }
+
+    #[test]
+    fn hash_ignores_counters() {
+        let a = ataxx::Board::from_fen("x5o/7/2-1-2/7/2-1-2/7/o5x x 0 1");
+        let b = ataxx::Board::from_fen("x5o/7/2-1-2/7/2-1-2/7/o5x x 20 40");
+        assert_eq!(a.hash(), b.hash());
+    }
+}
@@ -0,0 +1,178 @@
+#[cfg(test)]
+mod tests {
+    use ataxx::{
+        parse_openings, run_match, Board, EngineConfig, MatchConfig, MatchReport, Move, PgnReader,
+    };
+    use std::io::{BufRead, BufReader, Write};
+    use std::process::{Command, Stdio};
+    use std::time::{Duration, Instant};
+
+    fn random(name: &str, args: &[&str]) -> EngineConfig {
+        let mut config = EngineConfig::new(name, env!("CARGO_BIN_EXE_ataxx-random"));
+        config.args = args.iter().map(|arg| arg.to_string()).collect();
+        config
+    }
+
+    fn play(config: &MatchConfig) -> (MatchReport, String) {
+        let mut pgn = Vec::new();
+        let report = run_match(config, &mut pgn).unwrap();
+        (report, String::from_utf8(pgn).unwrap())
+    }
+
+    #[test]
+    fn random_movers() {
+        let mut config = MatchConfig::new([
+            random("first", &["--seed", "1"]),
+            random("second", &["--seed", "2"]),
+        ]);
+        config.openings = parse_openings(
+            "x5o/7/7/7/7/7/o5x x 0 1\n\
+             # comment\n\
+             x5o/7/2-1-2/7/2-1-2/7/o5x x 0 1 ;D1 14\n\
+             x5o/7/3-3/2-1-2/3-3/7/o5x o 0 1\n",
+        )
+        .unwrap();
+        config.concurrency = 2;
+
+        let (report, pgn) = play(&config);
+        assert_eq!(report.games.len(), 6);
+        assert_eq!(report.wins + report.losses + report.draws, 6);
+
+        for (idx, game) in report.games.iter().enumerate() {
+            assert_eq!(game.start, config.openings[idx / 2]);
+            assert_eq!(game.tag("Termination"), Some("normal"));
+            assert_eq!(
+                game.tag("Round"),
+                Some(format!("{}.{}", idx / 2 + 1, idx % 2 + 1).as_str())
+            );
+
+            let (black, white) = if idx % 2 == 0 {
+                ("first", "second")
+            } else {
+                ("second", "first")
+            };
+            assert_eq!(game.tag("Black"), Some(black));
+            assert_eq!(game.tag("White"), Some(white));
+
+            let replayed = game.to_game().unwrap();
+            assert!(replayed.is_gameover());
+            assert_eq!(game.outcome, replayed.result().map(|result| result.outcome));
+        }
+
+        // The PGN holds the same games, in the order they finished
+        let mut read: Vec<_> = PgnReader::new(pgn.as_bytes()).map(Result::unwrap).collect();
+        assert_eq!(read.len(), 6);
+        read.sort_by_key(|game| game.tag("Round").unwrap().to_string());
+        for (read, game) in read.iter().zip(&report.games) {
+            assert_eq!(read.moves, game.moves);
+            assert_eq!(read.outcome, game.outcome);
+        }
+    }
+
+    #[test]
+    fn crash() {
+        let mut config = MatchConfig::new([
+            random("crasher", &["--seed", "1", "--crash-after", "3"]),
+            random("random", &["--seed", "2"]),
+        ]);
+        config.openings = parse_openings("x5o/7/7/7/7/7/o5x x 0 1\n").unwrap();
+
+        let (report, _) = play(&config);
+        assert_eq!(report.losses, 2);
+
+        // The engine is restarted for the second game, where it crashes again
+        for (game, moves) in report.games.iter().zip([4, 5]) {
+            assert_eq!(game.tag("Termination"), Some("abandoned"));
+            assert_eq!(game.moves.len(), moves);
+            assert_eq!(game.comments, vec![(moves, "crasher crashed".to_string())]);
+        }
+    }
+
+    #[test]
+    fn hang() {
+        let mut config = MatchConfig::new([
+            random("random", &["--seed", "1"]),
+            random("hanger", &["--seed", "2", "--hang-after", "1"]),
+        ]);
+        config.time = Duration::from_millis(200);
+        config.increment = Duration::ZERO;
+        config.timeout = Duration::from_millis(200);
+
+        let start = Instant::now();
+        let (report, _) = play(&config);
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(report.wins, 2);
+
+        for game in &report.games {
+            assert_eq!(game.tag("Termination"), Some("time forfeit"));
+            assert_eq!(
+                game.comments.last().map(|(_, text)| text.as_str()),
+                Some("hanger stopped responding")
+            );
+        }
+    }
+
+    #[test]
+    fn random_position_after_repetition() {
+        let fen = "x5o/7/7/7/7/7/o5x x 0 1";
+        let moves = "a7c5 g7e5 c5a7 e5g7 a7c5 g7e5 c5a7 e5g7 a7c5";
+
+        let mut child = Command::new(env!("CARGO_BIN_EXE_ataxx-random"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut stdin = child.stdin.take().unwrap();
+        writeln!(stdin, "position fen {} moves {}\ngo\nquit", fen, moves).unwrap();
+
+        let lines: Vec<String> = BufReader::new(child.stdout.take().unwrap())
+            .lines()
+            .map(Result::unwrap)
+            .collect();
+        assert!(child.wait().unwrap().success());
+        assert_eq!(lines.len(), 1);
+
+        let mut pos = Board::from_fen(fen);
+        for mv in moves.split_whitespace() {
+            pos.makemove(&mv.parse().unwrap());
+        }
+        let mv: Move = lines[0].strip_prefix("bestmove ").unwrap().parse().unwrap();
+        assert!(pos.is_legal_move(&mv));
+    }
+
+    #[test]
+    fn missing_engine() {
+        let mut config = MatchConfig::new([
+            random("random", &[]),
+            EngineConfig::new("missing", "/nonexistent/engine"),
+        ]);
+        config.timeout = Duration::from_millis(500);
+
+        let (report, _) = play(&config);
+        assert_eq!(report.wins, 2);
+        assert_eq!(report.score(), 1.0);
+        for game in &report.games {
+            assert!(game.moves.is_empty());
+            assert_eq!(game.tag("Termination"), Some("abandoned"));
+        }
+
+        // Engines that never finish starting up
+        config.engines[1] = EngineConfig::new("silent", "sleep");
+        config.engines[1].args = vec!["10".to_string()];
+        let (report, _) = play(&config);
+        assert_eq!(report.wins, 2);
+        assert_eq!(report.games[0].tag("Termination"), Some("time forfeit"));
+    }
+
+    #[test]
+    fn rustaxx_beats_random() {
+        let mut rustaxx = EngineConfig::new("Rustaxx", env!("CARGO_BIN_EXE_ataxx-uai"));
+        rustaxx.options.push(("Hash".to_string(), "1".to_string()));
+        let mut config = MatchConfig::new([rustaxx, random("random", &["--seed", "3"])]);
+        config.time = Duration::from_secs(2);
+        config.increment = Duration::from_millis(20);
+
+        let (report, _) = play(&config);
+        assert_eq!(report.wins, 2, "{:?}", report.games);
+    }
+}
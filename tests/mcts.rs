@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use ataxx::{Board, Mcts, MctsConfig, Move, Playout, SearchLimits};
+    use std::time::Duration;
+
+    fn nodes(nodes: u64) -> SearchLimits {
+        SearchLimits {
+            nodes: Some(nodes),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn legal_best_move() {
+        for playout in [Playout::Random, Playout::Biased] {
+            let pos = Board::from_fen("x5o/7/2-1-2/7/2-1-2/7/o5x o 0 1");
+            let config = MctsConfig {
+                playout,
+                ..Default::default()
+            };
+            let mut mcts = Mcts::new(pos, config);
+            let result = mcts.search(&nodes(1000));
+
+            assert_eq!(result.nodes, 1000);
+            assert_eq!(mcts.visits(), 1000);
+            assert!(pos.is_legal_move(&result.best_move.unwrap()));
+            assert_eq!(result.moves.len(), pos.count_moves() as usize);
+            assert_eq!(result.moves.iter().map(|m| m.visits).sum::<u32>(), 1000);
+            assert!(result.moves.windows(2).all(|w| w[0].visits >= w[1].visits));
+            assert!(result
+                .moves
+                .iter()
+                .all(|m| (0.0..=1.0).contains(&m.win_rate)));
+        }
+    }
+
+    #[test]
+    fn finds_win() {
+        let pos = Board::from_fen("7/7/7/7/7/7/x1o4 x 0 1");
+        let mut mcts = Mcts::new(pos, MctsConfig::default());
+        let result = mcts.search(&nodes(2000));
+
+        assert!(pos
+            .after_move(&result.best_move.unwrap())
+            .white()
+            .is_empty());
+        assert_eq!(result.moves[0].win_rate, 1.0);
+    }
+
+    #[test]
+    fn forced_pass() {
+        let pos = Board::from_fen("7/7/7/7/ooooooo/ooooooo/xxxxxxx x 0 1");
+        let mut mcts = Mcts::new(pos, MctsConfig::default());
+        let result = mcts.search(&nodes(100));
+
+        assert_eq!(result.best_move, Some(Move::Pass));
+        assert_eq!(result.moves.len(), 1);
+        assert_eq!(result.moves[0].visits, 100);
+    }
+
+    #[test]
+    fn gameover() {
+        let pos = Board::from_fen("7/7/7/7/7/7/x6 o 0 1");
+        let mut mcts = Mcts::new(pos, MctsConfig::default());
+        let result = mcts.search(&nodes(100));
+
+        assert_eq!(result.best_move, None);
+        assert_eq!(result.nodes, 0);
+        assert!(result.moves.is_empty());
+    }
+
+    #[test]
+    fn movetime() {
+        let mut mcts = Mcts::new(Board::from_fen("startpos"), MctsConfig::default());
+        let result = mcts.search(&SearchLimits {
+            movetime: Some(Duration::from_millis(50)),
+            ..Default::default()
+        });
+
+        assert!(result.best_move.is_some());
+        assert!(result.nodes > 0);
+        assert!(result.time < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn tree_reuse() {
+        let pos = Board::from_fen("startpos");
+        let mut mcts = Mcts::new(pos, MctsConfig::default());
+        let first = mcts.search(&nodes(2000));
+
+        // Play our move, then the reply we spent the most time on
+        let ours = first.best_move.unwrap();
+        mcts.play(&ours);
+        let reply = mcts.root_moves()[0];
+        mcts.play(&reply.mv);
+
+        assert_eq!(mcts.board(), &pos.after_move(&ours).after_move(&reply.mv));
+        assert_eq!(mcts.visits(), reply.visits);
+        assert!(mcts.visits() > 0);
+        assert!(mcts.tree_size() < 2000);
+
+        let second = mcts.search(&nodes(500));
+        assert_eq!(mcts.visits(), reply.visits + 500);
+        assert!(mcts.board().is_legal_move(&second.best_move.unwrap()));
+
+        // The same through set_position
+        let mut other = Mcts::new(pos, MctsConfig::default());
+        other.search(&nodes(2000));
+        other.set_position(&pos.after_move(&ours).after_move(&reply.mv));
+        assert_eq!(other.visits(), reply.visits);
+
+        // Unknown positions start a new tree
+        other.set_position(&Board::from_fen("x5o/7/7/7/7/7/o5x o 0 1"));
+        assert_eq!(other.visits(), 0);
+        assert_eq!(other.tree_size(), 1);
+    }
+
+    #[test]
+    fn deterministic() {
+        let pos = Board::from_fen("x5o/7/3-3/2-1-2/3-3/7/o5x x 0 1");
+        let config = MctsConfig {
+            seed: 12345,
+            ..Default::default()
+        };
+        let a = Mcts::new(pos, config.clone()).search(&nodes(500));
+        let b = Mcts::new(pos, config).search(&nodes(500));
+        assert_eq!(a.best_move, b.best_move);
+        assert_eq!(a.moves, b.moves);
+    }
+}
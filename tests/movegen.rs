@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use ataxx::{Board, Move, Square};
+
+    #[test]
+    fn forced_pass() {
+        let tests: [&str; 3] = [
+            "7/7/7/7/ooooooo/ooooooo/xxxxxxx x 0 1",
+            "7/7/7/7/xxxxxxx/xxxxxxx/ooooooo o 0 1",
+            "7/7/7/7/-------/-------/xoo-3 x 50 1",
+        ];
+
+        for fen in tests {
+            let pos = Board::from_fen(fen);
+            assert_eq!(pos.legal_moves(), vec![Move::Pass]);
+            assert_eq!(pos.count_moves(), 1);
+            assert!(pos.can_pass());
+            assert!(pos.is_legal_move(&Move::Pass));
+        }
+    }
+
+    #[test]
+    fn agreement() {
+        let tests: [&str; 10] = [
+            "x5o/7/7/7/7/7/o5x x 0 1",
+            "x5o/7/2-1-2/7/2-1-2/7/o5x o 0 1",
+            "7/7/7/2x1o2/7/7/7 x 0 1",
+            "7/7/7/7/ooooooo/ooooooo/xxxxxxx x 0 1",
+            "7/7/7/7/ooooooo/ooooooo/xxxxxxx o 0 1",
+            "7/7/7/7/7/7/7 x 0 1",
+            "7/7/7/7/7/7/x6 x 0 1",
+            "x5o/7/7/7/7/7/o5x x 100 1",
+            "7/7/7/7/-------/-------/ooooxxx x 0 1",
+            "7/7/7/7/-------/-------/x5o o 0 1",
+        ];
+
+        for fen in tests {
+            let pos = Board::from_fen(fen);
+            let moves = pos.legal_moves();
+
+            assert_eq!(moves.len() as u64, pos.count_moves());
+            assert_eq!(moves.contains(&Move::Pass), pos.can_pass());
+            assert_eq!(pos.is_legal_move(&Move::Pass), pos.can_pass());
+            assert_eq!(moves.is_empty(), pos.is_gameover());
+            for mv in &moves {
+                assert!(pos.is_legal_move(mv));
+            }
+            for sq in Square::ALL {
+                assert_eq!(
+                    pos.is_legal_move(&Move::Drop(sq)),
+                    moves.contains(&Move::Drop(sq))
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod tests {
+    use ataxx::{Board, Move, MoveParseError, Square};
+
+    #[test]
+    fn legal() {
+        let pos = Board::from_fen("x5o/7/7/7/7/7/o5x x 0 1");
+        assert_eq!(pos.parse_move("b6"), Ok(Move::Drop(Square::B6)));
+        assert_eq!(
+            pos.parse_move("A7C5"),
+            Ok(Move::Jump(Square::A7, Square::C5))
+        );
+        assert_eq!(
+            pos.parse_move("g1-e3"),
+            Ok(Move::Jump(Square::G1, Square::E3))
+        );
+    }
+
+    #[test]
+    fn illegal() {
+        let pos = Board::from_fen("x5o/7/7/7/7/7/o5x x 0 1");
+        let tests = [
+            ("d4", MoveParseError::Illegal(Move::Drop(Square::D4))),
+            (
+                "a1c3",
+                MoveParseError::Illegal(Move::Jump(Square::A1, Square::C3)),
+            ),
+            ("0000", MoveParseError::Illegal(Move::Pass)),
+            (
+                "a7b6",
+                MoveParseError::SingleWrittenAsJump("a7b6".to_string()),
+            ),
+            ("a7", MoveParseError::Illegal(Move::Drop(Square::A7))),
+        ];
+
+        for (text, err) in tests {
+            assert_eq!(pos.parse_move(text), Err(err));
+        }
+    }
+
+    #[test]
+    fn pass() {
+        let pos = Board::from_fen("7/7/7/7/ooooooo/ooooooo/xxxxxxx x 0 1");
+        assert_eq!(pos.parse_move("pass"), Ok(Move::Pass));
+        assert_eq!(pos.parse_move("0000"), Ok(Move::Pass));
+    }
+}
@@ -1,84 +1,149 @@
 #[cfg(test)]
 mod tests {
+    const SUITE: &str = include_str!("../suites/edge.epd");
+
+    fn suite() -> Vec<ataxx::SuiteEntry> {
+        ataxx::parse_suite(SUITE).unwrap()
+    }
+
     #[test]
     fn perft_suite() {
-        let tests: [(&str, Vec<u64>); 20] = [
-            ("7/7/7/7/7/7/7 x 0 1", vec![1, 0, 0, 0, 0, 0]),
-            ("7/7/7/7/7/7/7 o 0 1", vec![1, 0, 0, 0, 0, 0]),
-            (
-                "x5o/7/7/7/7/7/o5x x 0 1",
-                vec![1, 16, 256, 6460, 155888, 4752668],
-            ),
-            (
-                "x5o/7/7/7/7/7/o5x o 0 1",
-                vec![1, 16, 256, 6460, 155888, 4752668],
-            ),
-            (
-                "x5o/7/2-1-2/7/2-1-2/7/o5x x 0 1",
-                vec![1, 14, 196, 4184, 86528, 2266352],
-            ),
-            (
-                "x5o/7/2-1-2/7/2-1-2/7/o5x o 0 1",
-                vec![1, 14, 196, 4184, 86528, 2266352],
-            ),
-            (
-                "x5o/7/2-1-2/3-3/2-1-2/7/o5x x 0 1",
-                vec![1, 14, 196, 4100, 83104, 2114588],
-            ),
-            (
-                "x5o/7/2-1-2/3-3/2-1-2/7/o5x o 0 1",
-                vec![1, 14, 196, 4100, 83104, 2114588],
-            ),
-            (
-                "x5o/7/3-3/2-1-2/3-3/7/o5x x 0 1",
-                vec![1, 16, 256, 5948, 133264, 3639856],
-            ),
-            (
-                "x5o/7/3-3/2-1-2/3-3/7/o5x o 0 1",
-                vec![1, 16, 256, 5948, 133264, 3639856],
-            ),
-            (
-                "7/7/7/7/ooooooo/ooooooo/xxxxxxx x 0 1",
-                vec![1, 1, 75, 249, 14270, 452980],
-            ),
-            (
-                "7/7/7/7/ooooooo/ooooooo/xxxxxxx o 0 1",
-                vec![1, 75, 249, 14270, 452980],
-            ),
-            (
-                "7/7/7/7/xxxxxxx/xxxxxxx/ooooooo x 0 1",
-                vec![1, 75, 249, 14270, 452980],
-            ),
-            (
-                "7/7/7/7/xxxxxxx/xxxxxxx/ooooooo o 0 1",
-                vec![1, 1, 75, 249, 14270, 452980],
-            ),
-            (
-                "7/7/7/2x1o2/7/7/7 x 0 1",
-                vec![1, 23, 419, 7887, 168317, 4266992],
-            ),
-            (
-                "7/7/7/2x1o2/7/7/7 o 0 1",
-                vec![1, 23, 419, 7887, 168317, 4266992],
-            ),
-            ("x5o/7/7/7/7/7/o5x x 100 1", vec![1, 0, 0, 0, 0, 0]),
-            ("x5o/7/7/7/7/7/o5x o 100 1", vec![1, 0, 0, 0, 0, 0]),
-            (
-                "7/7/7/7/-------/-------/x5o x 0 1",
-                vec![1, 2, 4, 13, 30, 73, 174],
-            ),
-            (
-                "7/7/7/7/-------/-------/x5o o 0 1",
-                vec![1, 2, 4, 13, 30, 73, 174],
-            ),
-        ];
-
-        for (fen, nodes) in tests {
-            let pos = ataxx::Board::from_fen(fen);
-            for (depth, expected) in nodes.iter().enumerate() {
-                let nodes: u64 = ataxx::perft(&pos, depth as u8);
-                assert_eq!(&nodes, expected);
+        let report = ataxx::run_suite(&suite(), u8::MAX);
+        assert_eq!(report.positions, 20);
+        assert_eq!(report.checks, 120);
+        assert_eq!(report.failures, vec![]);
+    }
+
+    #[test]
+    fn standard_suite() {
+        let suite = ataxx::standard_suite();
+        assert_eq!(suite.len(), 20);
+        assert!(suite.iter().all(|entry| entry.counts.len() == 6));
+
+        let report = ataxx::run_suite(&suite, 4);
+        assert_eq!(report.checks, 80);
+        assert!(report.passed());
+    }
+
+    #[test]
+    fn perft_parallel_suite() {
+        for threads in [0, 1, 3] {
+            let report = ataxx::run_suite_with(&suite(), u8::MAX, |pos, depth| {
+                ataxx::perft_parallel(pos, depth, threads)
+            });
+            assert!(report.passed());
+        }
+    }
+
+    #[test]
+    fn perft_hashed_suite() {
+        let table = ataxx::PerftTable::new(1);
+
+        let report = ataxx::run_suite_with(&suite(), u8::MAX, |pos, depth| {
+            ataxx::perft_hashed(pos, depth, &table).nodes
+        });
+        assert!(report.passed());
+    }
+
+    #[test]
+    fn perft_hashed_hits() {
+        let pos = ataxx::Board::from_fen("startpos");
+
+        // Even a single bucket gives the right counts
+        let tiny = ataxx::PerftTable::new(0);
+        assert_eq!(tiny.capacity(), 2);
+        assert_eq!(ataxx::perft_hashed(&pos, 4, &tiny).nodes, 155888);
+
+        // Transpositions need two moves by each side, so depth 6 before there are hits
+        let pos = ataxx::Board::from_fen("7/7/7/7/-------/-------/x5o x 0 1");
+        let table = ataxx::PerftTable::new(1);
+        let first = ataxx::perft_hashed(&pos, 6, &table);
+        assert_eq!(first.nodes, 174);
+        assert!(first.hits > 0);
+        assert!(first.hit_rate() > 0.0 && first.hit_rate() < 1.0);
+
+        let second = ataxx::perft_hashed(&pos, 6, &table);
+        assert_eq!(second.nodes, 174);
+        assert_eq!((second.probes, second.hits), (1, 1));
+        assert_eq!(second.hit_rate(), 1.0);
+    }
+
+    #[test]
+    fn perft_hashed_halfmoves() {
+        let table = ataxx::PerftTable::new(1);
+
+        for halfmoves in [0, 94, 95, 96, 97, 98, 99] {
+            let fen = format!("x5o/7/2-1-2/7/2-1-2/7/o5x x {} 1", halfmoves);
+            let pos = ataxx::Board::from_fen(&fen);
+            let expected = ataxx::perft(&pos, 5);
+            assert_eq!(ataxx::perft_hashed(&pos, 5, &table).nodes, expected);
+        }
+    }
+
+    #[test]
+    fn perft_divide_suite() {
+        for entry in suite() {
+            let pos = entry.board;
+            assert!(ataxx::perft_divide(&pos, 0).is_empty());
+
+            for &(depth, expected) in entry.counts.iter().skip(1) {
+                let divide = ataxx::perft_divide(&pos, depth);
+                assert_eq!(divide.iter().map(|(_, n)| n).sum::<u64>(), expected);
+                assert!(divide.iter().all(|(mv, _)| pos.is_legal_move(mv)));
+            }
+        }
+    }
+
+    #[test]
+    fn perft_divide_pass() {
+        let pos = ataxx::Board::from_fen("7/7/7/7/ooooooo/ooooooo/xxxxxxx x 0 1");
+        assert_eq!(ataxx::perft_divide(&pos, 3), vec![(ataxx::Move::Pass, 249)]);
+    }
+
+    #[test]
+    fn perft_stats_suite() {
+        for entry in suite() {
+            let stats = ataxx::perft_stats(&entry.board, entry.counts.len() as u8 - 1);
+
+            for &(depth, expected) in entry.counts.iter().skip(1) {
+                let stats = &stats[depth as usize - 1];
+                assert_eq!(stats.nodes, expected);
+                assert_eq!(stats.singles + stats.jumps + stats.passes, stats.nodes);
+                assert!(stats.gameovers <= stats.nodes);
             }
         }
     }
+
+    #[test]
+    fn perft_stats_breakdown() {
+        // Three of the seven moves capture the only white piece and end the game
+        let pos = ataxx::Board::from_fen("7/7/7/7/7/7/x1o4 x 0 1");
+        let stats = ataxx::perft_stats(&pos, 1);
+        assert_eq!(
+            stats,
+            vec![ataxx::PerftStats {
+                nodes: 7,
+                singles: 3,
+                jumps: 4,
+                passes: 0,
+                gameovers: 3,
+                flips: 3,
+            }]
+        );
+
+        let pos = ataxx::Board::from_fen("startpos");
+        let stats = ataxx::perft_stats(&pos, 3);
+        assert_eq!((stats[0].singles, stats[0].jumps), (6, 10));
+        assert_eq!(stats[0].flips, 0);
+        assert_eq!(stats[2].passes, 0);
+
+        let pos = ataxx::Board::from_fen("7/7/7/7/ooooooo/ooooooo/xxxxxxx x 0 1");
+        let stats = ataxx::perft_stats(&pos, 2);
+        assert_eq!(stats[0].passes, 1);
+        assert_eq!(stats[0].nodes, 1);
+        assert_eq!(stats[1].passes, 0);
+        assert_eq!(stats[1].nodes, 75);
+
+        assert!(ataxx::perft_stats(&pos, 0).is_empty());
+    }
 }
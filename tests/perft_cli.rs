@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use std::process::{Command, Output};
+
+    fn run(args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_ataxx-perft"))
+            .args(args)
+            .output()
+            .unwrap()
+    }
+
+    fn stdout(output: &Output) -> String {
+        String::from_utf8(output.stdout.clone()).unwrap()
+    }
+
+    #[test]
+    fn total() {
+        let output = run(&["--depth", "3", "--expect", "6460"]);
+        assert!(output.status.success());
+        assert!(stdout(&output).starts_with("nodes 6460 time "));
+    }
+
+    #[test]
+    fn divide() {
+        for hash in [&[][..], &["--hash", "1"][..]] {
+            let mut args = vec![
+                "--fen",
+                "x5o/7/2-1-2/7/2-1-2/7/o5x x 0 1",
+                "--depth",
+                "4",
+                "--divide",
+            ];
+            args.extend_from_slice(hash);
+            let output = run(&args);
+            assert!(output.status.success());
+
+            let text = stdout(&output);
+            let lines: Vec<&str> = text.lines().collect();
+            assert_eq!(lines.len(), 15);
+
+            let sum: u64 = lines[..14]
+                .iter()
+                .map(|line| line.split_once(' ').unwrap().1.parse::<u64>().unwrap())
+                .sum();
+            assert_eq!(sum, 86528);
+            assert!(lines[14].starts_with("nodes 86528 "));
+        }
+    }
+
+    #[test]
+    fn mismatch() {
+        let output = run(&["--depth", "2", "--expect", "257"]);
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("expected 257 nodes, got 256"));
+    }
+
+    #[test]
+    fn suite() {
+        let output = run(&["--suite", "standard", "--depth", "3"]);
+        assert!(output.status.success());
+        assert!(stdout(&output).starts_with("positions 20 checks 60 failures 0 "));
+
+        let path = std::env::temp_dir().join("ataxx-perft-cli-suite.epd");
+        std::fs::write(
+            &path,
+            "# wrong D2\nx5o/7/7/7/7/7/o5x x 0 1 ;D1 16 ;D2 257\n",
+        )
+        .unwrap();
+        let output = run(&["--suite", path.to_str().unwrap(), "--depth", "2"]);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(output.status.code(), Some(1));
+        let text = stdout(&output);
+        assert!(text.contains("fail x5o/7/7/7/7/7/o5x x 0 1 depth 2: expected 257 got 256"));
+        assert!(text.contains("positions 1 checks 2 failures 1 "));
+    }
+
+    #[test]
+    fn bad_arguments() {
+        for args in [
+            &["--depth"][..],
+            &["--depth", "x"][..],
+            &["--fen", "7/7/7 x 0 1"][..],
+            &["--frobnicate"][..],
+            &["--suite"][..],
+            &["--suite", "/nonexistent/suite.epd"][..],
+        ] {
+            assert_eq!(run(args).status.code(), Some(2));
+        }
+    }
+}
@@ -0,0 +1,287 @@
+#[cfg(test)]
+mod tests {
+    use ataxx::{
+        Board, Game, Move, MoveParseError, Outcome, PgnError, PgnErrorKind, PgnGame, PgnReader,
+        Square,
+    };
+    use std::io::Cursor;
+
+    fn read_all(text: &str) -> Vec<Result<PgnGame, PgnError>> {
+        PgnReader::new(Cursor::new(text)).collect()
+    }
+
+    #[test]
+    fn write() {
+        let game: Game = "startpos moves f1 a1c3 g2".parse().unwrap();
+        let mut pgn = PgnGame::from_game(&game);
+        pgn.set_tag("Event", "Test");
+        pgn.set_tag("Black", "Rustaxx");
+        pgn.set_tag("Annotator", "a \"quoted\" name");
+        pgn.comments.push((1, "book".to_string()));
+
+        assert_eq!(
+            pgn.to_string(),
+            "[Event \"Test\"]\n\
+             [Site \"?\"]\n\
+             [Date \"?\"]\n\
+             [Round \"?\"]\n\
+             [White \"?\"]\n\
+             [Black \"Rustaxx\"]\n\
+             [Result \"*\"]\n\
+             [Annotator \"a \\\"quoted\\\" name\"]\n\
+             \n\
+             1. f1 {book} 1... a1c3 2. g2 *\n\
+             \n"
+        );
+    }
+
+    #[test]
+    fn write_fen() {
+        let start = Board::from_fen("x5o/7/7/7/7/7/o5x o 0 7");
+        let mut pgn = PgnGame::new(start);
+        pgn.moves.push(Move::Drop(Square::B7));
+        pgn.outcome = Some(Outcome::Draw);
+
+        let text = pgn.to_string();
+        assert!(text
+            .contains("[Result \"1/2-1/2\"]\n[SetUp \"1\"]\n[FEN \"x5o/7/7/7/7/7/o5x o 0 7\"]\n"));
+        assert!(text.ends_with("\n7... b7 1/2-1/2\n\n"));
+    }
+
+    #[test]
+    fn sanitise() {
+        let game: Game = "startpos moves f1".parse().unwrap();
+        let mut pgn = PgnGame::from_game(&game);
+        pgn.set_tag("Event", "two\nlines\r\n");
+        pgn.comments.push((0, "engine played '}'".to_string()));
+        pgn.comments.push((1, "{nested}".to_string()));
+
+        let text = pgn.to_string();
+        assert!(text.contains("[Event \"twolines\"]\n"));
+
+        let read = PgnReader::new(Cursor::new(text)).next().unwrap().unwrap();
+        assert_eq!(read.tag("Event"), Some("twolines"));
+        assert_eq!(read.moves, pgn.moves);
+        assert_eq!(
+            read.comments,
+            vec![
+                (0, "engine played ''".to_string()),
+                (1, "{nested".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn wrapping() {
+        let mut game = Game::new(Board::from_fen("startpos"));
+        for _ in 0..40 {
+            let mv = game.board().legal_moves()[0];
+            game.play(mv).unwrap();
+        }
+        let text = PgnGame::from_game(&game).to_string();
+        let movetext = text.split("\n\n").nth(1).unwrap();
+        assert!(movetext.lines().count() > 1);
+        assert!(movetext.lines().all(|line| line.len() < 80));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut games = Vec::new();
+        for (idx, moves) in ["", " moves f1", " moves f1 a1c3 g2 g7e5"]
+            .iter()
+            .enumerate()
+        {
+            let game: Game = format!("fen x5o/7/7/7/7/7/o5x x 0 1{}", moves)
+                .parse()
+                .unwrap();
+            let mut pgn = PgnGame::from_game(&game);
+            pgn.set_tag("Round", &(idx + 1).to_string());
+            pgn.comments.push((0, "opening".to_string()));
+            games.push(pgn);
+        }
+        let mut finished = PgnGame::new(Board::from_fen("7/7/7/7/7/7/xo5 x 0 1"));
+        finished.moves.push(Move::Drop(Square::B2));
+        finished.outcome = Some(Outcome::BlackWin);
+        finished.set_tag("Round", "4");
+        games.push(finished);
+
+        let text: String = games.iter().map(|game| game.to_string()).collect();
+        let read: Vec<PgnGame> = read_all(&text).into_iter().map(Result::unwrap).collect();
+        assert_eq!(read.len(), games.len());
+
+        for (read, game) in read.iter().zip(&games) {
+            assert_eq!(read.start, game.start);
+            assert_eq!(read.moves, game.moves);
+            assert_eq!(read.comments, game.comments);
+            assert_eq!(read.outcome, game.outcome);
+            assert_eq!(read.tag("Round"), game.tag("Round"));
+            assert_eq!(read.to_string(), game.to_string());
+        }
+    }
+
+    #[test]
+    fn read() {
+        let text = "\
+[Event \"Casual\"]
+[White \"o\"]
+[Black \"x\"]
+[Result \"0-1\"]
+
+{A comment
+over two lines} 1.f1 a1c3 ; rest of the line
+2. g2 {short} 2...g7e5
+0-1
+";
+        let games = read_all(text);
+        assert_eq!(games.len(), 1);
+        let game = games[0].clone().unwrap();
+
+        assert_eq!(game.tag("Event"), Some("Casual"));
+        assert_eq!(game.tag("Site"), None);
+        assert_eq!(game.start, Board::from_fen("startpos"));
+        assert_eq!(
+            game.moves,
+            vec![
+                Move::Drop(Square::F1),
+                Move::Jump(Square::A1, Square::C3),
+                Move::Drop(Square::G2),
+                Move::Jump(Square::G7, Square::E5),
+            ]
+        );
+        assert_eq!(
+            game.comments,
+            vec![
+                (0, "A comment over two lines".to_string()),
+                (2, "rest of the line".to_string()),
+                (3, "short".to_string()),
+            ]
+        );
+        assert_eq!(game.outcome, Some(Outcome::BlackWin));
+        assert_eq!(game.to_game().unwrap().moves(), game.moves.as_slice());
+    }
+
+    #[test]
+    fn missing_results() {
+        // Games without a terminating result, one ended by the next game's tags
+        let text = "[Result \"1-0\"]\n1. f1\n[Event \"Second\"]\n1. f1 a1c3";
+        let games: Vec<PgnGame> = read_all(text).into_iter().map(Result::unwrap).collect();
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].outcome, Some(Outcome::WhiteWin));
+        assert_eq!(games[0].moves.len(), 1);
+        assert_eq!(games[1].tag("Event"), Some("Second"));
+        assert_eq!(games[1].outcome, None);
+        assert_eq!(games[1].moves.len(), 2);
+
+        assert!(read_all("").is_empty());
+        assert!(read_all("\n\n  \n").is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        let tests = [
+            (
+                "[Event \"x\"]\n\n1. f1 d4 *\n",
+                3,
+                7,
+                PgnErrorKind::InvalidMove(MoveParseError::Illegal(Move::Drop(Square::D4))),
+            ),
+            (
+                "1. f1 a1c3\n  2. g2 z9 *\n",
+                2,
+                9,
+                PgnErrorKind::InvalidMove(MoveParseError::InvalidSquare("z9".to_string())),
+            ),
+            (
+                "1. f1 a1c3 2.a1b1 *\n",
+                1,
+                14,
+                PgnErrorKind::InvalidMove(MoveParseError::SingleWrittenAsJump("a1b1".to_string())),
+            ),
+            (
+                "[Event \"x\"\n1. f1 *\n",
+                1,
+                1,
+                PgnErrorKind::InvalidTag("[Event \"x\"".to_string()),
+            ),
+            (
+                "1. f1 {never closed\n\n",
+                1,
+                7,
+                PgnErrorKind::UnterminatedComment,
+            ),
+            (
+                "1. f1 * a1c3\n",
+                1,
+                9,
+                PgnErrorKind::UnexpectedToken("a1c3".to_string()),
+            ),
+            (
+                "[FEN \"7/7/7/7/7/7/xo5 x 0 1\"]\n1. b2 0000\n",
+                2,
+                7,
+                PgnErrorKind::MoveAfterGameOver(Move::Pass),
+            ),
+        ];
+
+        for (text, line, column, kind) in tests {
+            let games = read_all(text);
+            assert_eq!(games.len(), 1, "{}", text);
+            assert_eq!(games[0], Err(PgnError { line, column, kind }), "{}", text);
+        }
+
+        let err = read_all("[FEN \"7/7/7 x 0 1\"]\n*\n")[0]
+            .clone()
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert!(matches!(err.kind, PgnErrorKind::Fen(_)));
+        assert!(err.to_string().starts_with("line 1 column 1: "));
+    }
+
+    #[test]
+    fn recover() {
+        // The rest of a bad game is skipped and the next one is still read
+        let text = "\
+[Event \"Bad tag\"
+[Round \"1\"]
+
+1. f1 *
+
+[Round \"2\"]
+
+1. f1 d4 a1c3 *
+
+[Round \"3\"]
+
+1. f1 *
+";
+        let games = read_all(text);
+        assert_eq!(games.len(), 3);
+        assert_eq!(games[0].clone().unwrap_err().line, 1);
+        assert_eq!(games[1].clone().unwrap_err().line, 8);
+        assert_eq!(games[2].clone().unwrap().tag("Round"), Some("3"));
+    }
+
+    #[test]
+    fn recover_fen() {
+        // Tags after a bad FEN belong to the same game and are skipped with it
+        let text = "\
+[Round \"1\"]
+[FEN \"x5o/7/7/7/7/7/o5 x 0 1\"]
+[TimeControl \"10+0.1\"]
+
+1. a7 *
+
+[Round \"2\"]
+
+1. f1 *
+";
+        let games = read_all(text);
+        assert_eq!(games.len(), 2);
+        assert!(matches!(
+            games[0].clone().unwrap_err().kind,
+            PgnErrorKind::Fen(_)
+        ));
+        assert_eq!(games[0].clone().unwrap_err().line, 2);
+        assert_eq!(games[1].clone().unwrap().tag("Round"), Some("2"));
+    }
+}
@@ -0,0 +1,269 @@
+#[cfg(test)]
+mod tests {
+    use ataxx::{
+        search, search_threads, search_with_eval, search_with_info, win_distance, Bitboard, Board,
+        Clocks, Evaluator, HandcraftedEval, Move, SearchLimits, Square, TranspositionTable,
+        Weights, WIN_SCORE,
+    };
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    fn depth(depth: u8) -> SearchLimits {
+        SearchLimits {
+            depth: Some(depth),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn legal_best_move() {
+        let tests: [&str; 4] = [
+            "x5o/7/7/7/7/7/o5x x 0 1",
+            "x5o/7/2-1-2/7/2-1-2/7/o5x o 0 1",
+            "7/7/7/2x1o2/7/7/7 x 0 1",
+            "x5o/6-/1-4-/-3--1/2-4/7/o-3-x x 0 1",
+        ];
+
+        for fen in tests {
+            let pos = Board::from_fen(fen);
+            let result = search(&pos, &depth(3));
+            assert_eq!(result.depth, 3);
+            assert!(pos.is_legal_move(&result.best_move.unwrap()));
+            assert_eq!(result.pv.first(), result.best_move.as_ref());
+            assert!(result.nodes > 0);
+
+            let mut npos = pos;
+            for mv in &result.pv {
+                assert!(npos.is_legal_move(mv));
+                npos.makemove(mv);
+            }
+        }
+    }
+
+    #[test]
+    fn immediate_win() {
+        let pos = Board::from_fen("7/7/7/7/7/1o5/x6 x 0 1");
+        let result = search(&pos, &depth(4));
+        assert_eq!(win_distance(result.score), Some(1));
+        assert!(pos
+            .after_move(&result.best_move.unwrap())
+            .white()
+            .is_empty());
+    }
+
+    #[test]
+    fn bigger_win() {
+        // Singles next to b2 win with three pieces to none, jumps with two
+        let pos = Board::from_fen("7/7/7/7/7/1o5/x6 x 0 1");
+        let jump = SearchLimits {
+            searchmoves: vec![Move::Jump(Square::A1, Square::C3)],
+            ..depth(4)
+        };
+        let jump = search(&pos, &jump);
+        let best = search(&pos, &depth(4));
+
+        assert_eq!(win_distance(jump.score), Some(1));
+        assert_eq!(win_distance(best.score), Some(1));
+        assert_eq!(best.score, jump.score + 1);
+        assert!(matches!(best.best_move, Some(Move::Drop(_))));
+    }
+
+    #[test]
+    fn forced_pass() {
+        let pos = Board::from_fen("7/7/7/7/ooooooo/ooooooo/xxxxxxx x 0 1");
+        let result = search(&pos, &depth(2));
+        assert_eq!(result.best_move, Some(Move::Pass));
+    }
+
+    #[test]
+    fn gameover() {
+        let pos = Board::from_fen("7/7/7/7/7/7/x6 o 0 1");
+        let result = search(&pos, &depth(2));
+        assert_eq!(result.best_move, None);
+        assert_eq!(result.score, -WIN_SCORE - 1);
+        assert_eq!(win_distance(result.score), Some(0));
+
+        // Drawn by the halfmove limit, the extra piece doesn't count
+        let pos = Board::from_fen("xx4o/7/7/7/7/7/o5x x 100 1");
+        let result = search(&pos, &depth(2));
+        assert_eq!(result.best_move, None);
+        assert_eq!(result.score, 0);
+    }
+
+    #[test]
+    fn limits() {
+        let pos = Board::from_fen("startpos");
+
+        let result = search(
+            &pos,
+            &SearchLimits {
+                nodes: Some(5000),
+                ..Default::default()
+            },
+        );
+        assert!(result.best_move.is_some());
+        assert!(result.nodes <= 5000 + 1);
+
+        let result = search(
+            &pos,
+            &SearchLimits {
+                movetime: Some(Duration::from_millis(50)),
+                ..Default::default()
+            },
+        );
+        assert!(result.best_move.is_some());
+        assert!(result.time < Duration::from_secs(2));
+
+        let limits = SearchLimits::default();
+        limits.stop.store(true, Ordering::Relaxed);
+        let result = search(&pos, &limits);
+        assert_eq!(result.depth, 1);
+        assert!(result.best_move.is_some());
+    }
+
+    #[test]
+    fn clocks() {
+        let pos = Board::from_fen("startpos");
+        let result = search(
+            &pos,
+            &SearchLimits {
+                clocks: Some(Clocks {
+                    btime: Duration::from_millis(300),
+                    wtime: Duration::from_secs(3600),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        );
+        assert!(result.best_move.is_some());
+        assert!(result.time < Duration::from_millis(300));
+    }
+
+    #[test]
+    fn single_thread() {
+        let pos = Board::from_fen("x5o/7/3-3/2-1-2/3-3/7/o5x x 0 1");
+        let a = search_with_info(&pos, &depth(5), &TranspositionTable::new(1), |_| {});
+        let b = search_threads(&pos, &depth(5), &TranspositionTable::new(1), 1, |_| {});
+        assert_eq!(
+            (a.best_move, a.score, a.depth, a.pv, a.nodes),
+            (b.best_move, b.score, b.depth, b.pv, b.nodes)
+        );
+    }
+
+    #[test]
+    fn threads() {
+        let tests: [&str; 3] = [
+            "x5o/7/7/7/7/7/o5x x 0 1",
+            "x5o/7/2-1-2/7/2-1-2/7/o5x o 0 1",
+            "x5o/6-/1-4-/-3--1/2-4/7/o-3-x x 0 1",
+        ];
+
+        for fen in tests {
+            let pos = Board::from_fen(fen);
+            let tt = TranspositionTable::new(4);
+            let mut iterations = 0;
+            let result = search_threads(&pos, &depth(4), &tt, 4, |_| iterations += 1);
+            assert_eq!(iterations, 4);
+            assert!(result.depth >= 4);
+            assert!(pos.is_legal_move(&result.best_move.unwrap()));
+            assert_eq!(result.pv.first(), result.best_move.as_ref());
+        }
+
+        let pos = Board::from_fen("7/7/7/7/7/1o5/x6 x 0 1");
+        let result = search_threads(&pos, &depth(4), &TranspositionTable::new(1), 3, |_| {});
+        assert_eq!(win_distance(result.score), Some(1));
+    }
+
+    /// Only cares about having a piece on one square
+    struct Likes(Square);
+
+    impl Evaluator for Likes {
+        fn eval(&self, pos: &Board) -> i32 {
+            let sq = Bitboard::from_square(self.0);
+            if !(pos.us() & sq).is_empty() {
+                1000
+            } else if !(pos.them() & sq).is_empty() {
+                -1000
+            } else {
+                0
+            }
+        }
+    }
+
+    #[test]
+    fn evaluator() {
+        let pos = Board::from_fen("startpos");
+        for (sq, threads) in [(Square::B6, 1), (Square::E1, 1), (Square::F2, 2)] {
+            let result = search_with_eval(
+                &pos,
+                &depth(1),
+                &TranspositionTable::new(1),
+                &Likes(sq),
+                threads,
+                |_| {},
+            );
+            assert_eq!(result.score, 1000);
+            assert!(!(pos.after_move(&result.best_move.unwrap()).black()
+                & Bitboard::from_square(sq))
+            .is_empty());
+        }
+
+        // Tuned weights reach the search
+        let pos = Board::from_fen("x5o/7/2-1-2/7/2-1-2/7/o5x x 0 1");
+        let eval = HandcraftedEval {
+            weights: Weights {
+                material: 7,
+                ..Default::default()
+            },
+        };
+        let tuned = search_with_eval(
+            &pos,
+            &depth(1),
+            &TranspositionTable::new(1),
+            &eval,
+            1,
+            |_| {},
+        );
+        let default = search(&pos, &depth(1));
+        assert_ne!(tuned.score, default.score);
+    }
+
+    #[test]
+    fn threads_limits() {
+        let pos = Board::from_fen("startpos");
+        let limits = SearchLimits {
+            nodes: Some(20_000),
+            ..Default::default()
+        };
+        let result = search_threads(&pos, &limits, &TranspositionTable::new(1), 3, |_| {});
+        assert!(result.best_move.is_some());
+        assert!(result.nodes <= 20_000, "{} nodes", result.nodes);
+
+        // Helpers search deeper than the main thread, but never past the depth limit
+        let result = search_threads(&pos, &depth(3), &TranspositionTable::new(1), 3, |_| {});
+        assert_eq!(result.depth, 3);
+    }
+
+    #[test]
+    fn threads_stop() {
+        let pos = Board::from_fen("startpos");
+        let limits = SearchLimits {
+            movetime: Some(Duration::from_millis(50)),
+            ..Default::default()
+        };
+        let result = search_threads(&pos, &limits, &TranspositionTable::new(1), 3, |_| {});
+        assert!(result.best_move.is_some());
+        assert!(result.time < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn deterministic() {
+        let pos = Board::from_fen("x5o/7/3-3/2-1-2/3-3/7/o5x x 0 1");
+        let a = search(&pos, &depth(4));
+        let b = search(&pos, &depth(4));
+        assert_eq!(
+            (a.best_move, a.score, a.pv, a.nodes),
+            (b.best_move, b.score, b.pv, b.nodes)
+        );
+    }
+}
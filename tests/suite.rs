@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use ataxx::{parse_suite, run_suite, Board, FenError, SuiteEntry, SuiteError, SuiteLineError};
+
+    #[test]
+    fn parse() {
+        let entry: SuiteEntry = "x5o/7/7/7/7/7/o5x x 0 1 ;D1 16 ;D2 256".parse().unwrap();
+        assert_eq!(entry.fen, "x5o/7/7/7/7/7/o5x x 0 1");
+        assert_eq!(entry.board, Board::from_fen("startpos"));
+        assert_eq!(entry.counts, vec![(1, 16), (2, 256)]);
+
+        // Spaces around the separators are optional
+        let entry: SuiteEntry = "x5o/7/7/7/7/7/o5x x 0 1;D3 6460;D1  16".parse().unwrap();
+        assert_eq!(entry.counts, vec![(3, 6460), (1, 16)]);
+
+        let entry: SuiteEntry = "x5o/7/7/7/7/7/o5x x 0 1".parse().unwrap();
+        assert!(entry.counts.is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        let tests = [
+            (
+                "x5o/7/7/7/7/7/o5x x 0 ;D1 16",
+                SuiteError::Fen(FenError::WrongFieldCount(3)),
+            ),
+            (
+                "x5o/7/7/7/7/7/o5x x 0 1 ;1 16",
+                SuiteError::InvalidDepth("1".to_string()),
+            ),
+            (
+                "x5o/7/7/7/7/7/o5x x 0 1 ;Dx 16",
+                SuiteError::InvalidDepth("Dx".to_string()),
+            ),
+            (
+                "x5o/7/7/7/7/7/o5x x 0 1 ;D1 sixteen",
+                SuiteError::InvalidCount("sixteen".to_string()),
+            ),
+            (
+                "x5o/7/7/7/7/7/o5x x 0 1 ;D1",
+                SuiteError::InvalidCount("D1".to_string()),
+            ),
+        ];
+
+        for (line, err) in tests {
+            assert_eq!(line.parse::<SuiteEntry>(), Err(err));
+        }
+    }
+
+    #[test]
+    fn parse_file() {
+        let text = "# comment\n\nx5o/7/7/7/7/7/o5x x 0 1 ;D1 16\n  \n7/7/7/7/7/7/7 x 0 1 ;D1 0\n";
+        let suite = parse_suite(text).unwrap();
+        assert_eq!(suite.len(), 2);
+
+        let text = "x5o/7/7/7/7/7/o5x x 0 1 ;D1 16\n\nx5o/7/7/7/7/7/o5x x 0 1 ;D1 x\n";
+        let err = parse_suite(text).unwrap_err();
+        assert_eq!(
+            err,
+            SuiteLineError {
+                line: 3,
+                error: SuiteError::InvalidCount("x".to_string()),
+            }
+        );
+        assert_eq!(err.to_string(), "line 3: invalid node count 'x'");
+    }
+
+    #[test]
+    fn failures() {
+        let suite = parse_suite("x5o/7/7/7/7/7/o5x x 0 1 ;D1 16 ;D2 255 ;D3 6460 ;D4 1").unwrap();
+
+        let report = run_suite(&suite, 3);
+        assert_eq!(report.positions, 1);
+        assert_eq!(report.checks, 3);
+        assert_eq!(report.nodes, 16 + 256 + 6460);
+        assert!(!report.passed());
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].depth, 2);
+        assert_eq!(report.failures[0].expected, 255);
+        assert_eq!(report.failures[0].actual, 256);
+        assert_eq!(
+            report.failures[0].to_string(),
+            "x5o/7/7/7/7/7/o5x x 0 1 depth 2: expected 255 got 256"
+        );
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use ataxx::{Board, Transform};
+
+    const FENS: [&str; 6] = [
+        "x5o/7/7/7/7/7/o5x x 0 1",
+        "x5o/7/2-1-2/7/2-1-2/7/o5x o 0 1",
+        "x5o/6-/1-4-/-3--1/2-4/7/o-3-x x 0 1",
+        "7/7/7/2x1o2/7/7/7 x 0 1",
+        "7/7/7/7/-------/-------/x5o x 0 1",
+        "1x5/2o4/7/3-3/7/7/xx5 o 12 30",
+    ];
+
+    #[test]
+    fn perft_invariant() {
+        for fen in FENS {
+            let pos = Board::from_fen(fen);
+            let nodes = ataxx::perft(&pos, 3);
+
+            for t in Transform::ALL {
+                let npos = pos.transform(t);
+                assert!(npos.is_valid());
+                assert_eq!(npos.hash(), npos.calculate_hash());
+                assert_eq!(npos.transform(t.inverse()), pos);
+                assert_eq!(ataxx::perft(&npos, 3), nodes);
+            }
+
+            let swapped = pos.swap_colours();
+            assert_eq!(swapped.turn(), !pos.turn());
+            assert_eq!(swapped.black(), pos.white());
+            assert_eq!(swapped.swap_colours(), pos);
+            assert_eq!(ataxx::perft(&swapped, 3), nodes);
+        }
+    }
+
+    #[test]
+    fn moves() {
+        for fen in FENS {
+            let pos = Board::from_fen(fen);
+
+            for t in Transform::ALL {
+                let npos = pos.transform(t);
+                for mv in pos.legal_moves() {
+                    assert_eq!(
+                        pos.after_move(&mv).transform(t),
+                        npos.after_move(&mv.transform(t))
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn canonical() {
+        for fen in FENS {
+            let pos = Board::from_fen(fen);
+            let (canonical, t) = pos.canonical();
+            assert_eq!(pos.transform(t), canonical);
+
+            for t in Transform::ALL {
+                assert_eq!(pos.transform(t).canonical().0, canonical);
+            }
+        }
+
+        let a = Board::from_fen("x6/7/7/7/7/7/6o x 0 1");
+        let b = Board::from_fen("6o/7/7/7/7/7/x6 x 0 1");
+        assert_eq!(a.canonical().0, b.canonical().0);
+
+        let symmetric = Board::from_fen("startpos");
+        assert_eq!(symmetric.canonical(), (symmetric, Transform::Identity));
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use ataxx::{
+        search_with_info, Board, Bound, Move, SearchLimits, Square, TTEntry, TranspositionTable,
+    };
+
+    fn entry(depth: u8, score: i32) -> TTEntry {
+        TTEntry {
+            depth,
+            score,
+            bound: Bound::Exact,
+            mv: Some(Move::Jump(Square::A1, Square::C3)),
+        }
+    }
+
+    #[test]
+    fn probe_store() {
+        let tt = TranspositionTable::new(1);
+        let key = Board::from_fen("startpos").hash();
+        assert_eq!(tt.probe(key), None);
+
+        tt.store(key, entry(5, -123));
+        assert_eq!(tt.probe(key), Some(entry(5, -123)));
+        assert_eq!(tt.probe(key ^ 1), None);
+    }
+
+    #[test]
+    fn keeps_move() {
+        let tt = TranspositionTable::new(1);
+        tt.store(1, entry(5, 10));
+        tt.store(
+            1,
+            TTEntry {
+                depth: 6,
+                score: 20,
+                bound: Bound::Upper,
+                mv: None,
+            },
+        );
+
+        let stored = tt.probe(1).unwrap();
+        assert_eq!(stored.depth, 6);
+        assert_eq!(stored.bound, Bound::Upper);
+        assert_eq!(stored.mv, entry(5, 10).mv);
+    }
+
+    #[test]
+    fn clear_resize() {
+        let mut tt = TranspositionTable::new(1);
+        assert_eq!(tt.capacity(), 1024 * 1024 / 16);
+
+        tt.store(42, entry(1, 1));
+        tt.clear();
+        assert_eq!(tt.probe(42), None);
+
+        tt.store(42, entry(1, 1));
+        tt.resize(2);
+        assert_eq!(tt.capacity(), 2 * 1024 * 1024 / 16);
+        assert_eq!(tt.probe(42), None);
+
+        tt.resize(0);
+        assert_eq!(tt.capacity(), 4);
+        tt.store(42, entry(1, 1));
+        assert_eq!(tt.probe(42), Some(entry(1, 1)));
+    }
+
+    #[test]
+    fn replacement() {
+        // A single bucket, so every key competes for the same four slots
+        let tt = TranspositionTable::new(0);
+        for key in 1..=4 {
+            tt.store(key, entry(10 + key as u8, 0));
+        }
+
+        // The shallowest entry makes way
+        tt.store(5, entry(1, 0));
+        assert_eq!(tt.probe(1), None);
+        assert!((2..=5).all(|key| tt.probe(key).is_some()));
+
+        // Entries from older searches make way even when they are deeper
+        tt.new_search();
+        tt.store(6, entry(10, 0));
+        tt.store(7, entry(10, 0));
+        tt.store(8, entry(10, 0));
+        assert!(tt.probe(4).is_some());
+        assert_eq!(tt.probe(5), None);
+        assert_eq!(tt.probe(2), None);
+        assert_eq!(tt.probe(3), None);
+    }
+
+    #[test]
+    fn hashfull() {
+        let tt = TranspositionTable::new(1);
+        assert_eq!(tt.hashfull(), 0);
+
+        for key in 0..100_000u64 {
+            tt.store(key.wrapping_mul(0x9e37_79b9_7f4a_7c15), entry(1, 0));
+        }
+        assert!(tt.hashfull() > 500);
+
+        tt.new_search();
+        assert_eq!(tt.hashfull(), 0);
+    }
+
+    #[test]
+    fn shared_search() {
+        let pos = Board::from_fen("x5o/7/3-3/2-1-2/3-3/7/o5x x 0 1");
+        let limits = SearchLimits {
+            depth: Some(4),
+            ..Default::default()
+        };
+        let tt = TranspositionTable::new(1);
+
+        let first = search_with_info(&pos, &limits, &tt, |_| {});
+        assert!(first.hashfull > 0);
+        assert!(tt.probe(pos.hash()).is_some());
+
+        let second = search_with_info(&pos, &limits, &tt, |_| {});
+        assert_eq!(first.best_move, second.best_move);
+        assert_eq!(first.score, second.score);
+        assert!(second.nodes < first.nodes);
+    }
+}
@@ -0,0 +1,220 @@
+#[cfg(test)]
+mod tests {
+    use ataxx::{Board, Move};
+    use std::io::{BufRead, BufReader, Write};
+    use std::process::{Command, Stdio};
+
+    /// Sends `input` and reads output until a line starting with `until`, then quits
+    fn run(input: &str, until: &str) -> Vec<String> {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_ataxx-uai"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let mut stdin = child.stdin.take().unwrap();
+        stdin.write_all(input.as_bytes()).unwrap();
+
+        let mut lines = Vec::new();
+        for line in BufReader::new(child.stdout.take().unwrap()).lines() {
+            let line = line.unwrap();
+            let done = line.starts_with(until);
+            lines.push(line);
+            if done {
+                break;
+            }
+        }
+
+        stdin.write_all(b"quit\n").unwrap();
+        assert!(child.wait().unwrap().success());
+        lines
+    }
+
+    fn bestmove(lines: &[String]) -> &str {
+        lines
+            .iter()
+            .rev()
+            .find_map(|line| line.strip_prefix("bestmove "))
+            .unwrap()
+    }
+
+    #[test]
+    fn handshake() {
+        let lines = run("uai\nisready\n", "readyok");
+        assert!(lines.iter().any(|l| l.starts_with("id name")));
+        assert!(lines.iter().any(|l| l.starts_with("option name Hash")));
+        assert_eq!(lines[lines.len() - 2], "uaiok");
+        assert_eq!(lines[lines.len() - 1], "readyok");
+    }
+
+    #[test]
+    fn go() {
+        let fen = "x5o/7/2-1-2/7/2-1-2/7/o5x x 0 1";
+        let input = format!(
+            "uainewgame\nsetoption name Hash value 4\nposition fen {} moves b6 b2\ngo depth 3\n",
+            fen
+        );
+        let lines = run(&input, "bestmove");
+        assert!(lines.iter().any(|l| l.starts_with("info depth 3 score cp")));
+
+        let mut pos = Board::from_fen(fen);
+        pos.makemove(&"b6".parse().unwrap());
+        pos.makemove(&"b2".parse().unwrap());
+        let mv: Move = bestmove(&lines).parse().unwrap();
+        assert!(pos.is_legal_move(&mv));
+    }
+
+    #[test]
+    fn position_after_repetition() {
+        let fen = "x5o/7/7/7/7/7/o5x x 0 1";
+        let moves = "a7c5 g7e5 c5a7 e5g7 a7c5 g7e5 c5a7 e5g7 a7c5";
+        let input = format!("position fen {} moves {}\ngo depth 2\n", fen, moves);
+        let lines = run(&input, "bestmove");
+        assert!(!lines.iter().any(|l| l.starts_with("info string")));
+
+        let mut pos = Board::from_fen(fen);
+        for mv in moves.split_whitespace() {
+            pos.makemove(&mv.parse().unwrap());
+        }
+        let mv: Move = bestmove(&lines).parse().unwrap();
+        assert!(pos.is_legal_move(&mv));
+    }
+
+    #[test]
+    fn bad_position_resets() {
+        let input = "position startpos moves g1e1\nposition startpos moves a1a2\ngo depth 1\n";
+        let lines = run(input, "bestmove");
+        assert!(lines.iter().any(|l| l.starts_with("info string")));
+
+        let mv: Move = bestmove(&lines).parse().unwrap();
+        assert!(Board::from_fen("startpos").is_legal_move(&mv));
+    }
+
+    #[test]
+    fn threads() {
+        let lines = run(
+            "setoption name Threads value 3\nposition startpos moves f2\ngo depth 4\n",
+            "bestmove",
+        );
+        assert!(lines.iter().any(|l| l.starts_with("info depth 4")));
+
+        let mut pos = Board::from_fen("startpos");
+        pos.makemove(&"f2".parse().unwrap());
+        let mv: Move = bestmove(&lines).parse().unwrap();
+        assert!(pos.is_legal_move(&mv));
+    }
+
+    #[test]
+    fn clock() {
+        let lines = run(
+            "position startpos\ngo wtime 1000 btime 1000 winc 10 binc 10\n",
+            "bestmove",
+        );
+        let mv: Move = bestmove(&lines).parse().unwrap();
+        assert!(Board::from_fen("startpos").is_legal_move(&mv));
+    }
+
+    #[test]
+    fn searchmoves() {
+        let lines = run(
+            "position startpos\ngo depth 3 searchmoves g1e3\n",
+            "bestmove",
+        );
+        assert_eq!(bestmove(&lines), "g1e3");
+    }
+
+    #[test]
+    fn stop() {
+        let lines = run("position startpos\ngo infinite\nstop\nisready\n", "readyok");
+        assert_eq!(
+            lines.iter().filter(|l| l.starts_with("bestmove")).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn infinite_waits_for_stop() {
+        // The search finishes at once, but bestmove must still wait for stop
+        let mut child = Command::new(env!("CARGO_BIN_EXE_ataxx-uai"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut stdin = child.stdin.take().unwrap();
+        let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+        let mut read_until = |prefix: &str| -> Vec<String> {
+            let mut read = Vec::new();
+            for line in lines.by_ref() {
+                let line = line.unwrap();
+                let done = line.starts_with(prefix);
+                read.push(line);
+                if done {
+                    break;
+                }
+            }
+            read
+        };
+
+        for fen in ["7/7/7/7/7/1o5/x6 x 0 1", "7/7/7/7/7/7/x6 o 0 1"] {
+            writeln!(stdin, "position fen {}\ngo infinite", fen).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            writeln!(stdin, "isready").unwrap();
+            let before = read_until("readyok");
+            assert!(!before.iter().any(|l| l.starts_with("bestmove")));
+
+            writeln!(stdin, "stop").unwrap();
+            assert!(read_until("bestmove")
+                .last()
+                .unwrap()
+                .starts_with("bestmove"));
+        }
+
+        writeln!(stdin, "quit").unwrap();
+        assert!(child.wait().unwrap().success());
+    }
+
+    #[test]
+    fn gameover() {
+        let lines = run(
+            "position fen 7/7/7/7/7/7/x6 o 0 1\ngo depth 3\n",
+            "bestmove",
+        );
+        assert_eq!(bestmove(&lines), "0000");
+    }
+
+    #[test]
+    fn errors() {
+        let lines = run(
+            "position fen nonsense\ngo depth x\nfoo\nisready\n",
+            "readyok",
+        );
+        assert_eq!(
+            lines
+                .iter()
+                .filter(|l| l.starts_with("info string"))
+                .count(),
+            3
+        );
+        assert!(lines.contains(&"bestmove 0000".to_string()));
+        assert_eq!(lines.last().unwrap(), "readyok");
+    }
+
+    #[test]
+    fn bench() {
+        let nodes = |lines: Vec<String>| -> String {
+            lines
+                .iter()
+                .find_map(|l| l.strip_prefix("nodes "))
+                .unwrap()
+                .split_whitespace()
+                .next()
+                .unwrap()
+                .to_string()
+        };
+
+        let a = nodes(run("bench 3\n", "nodes"));
+        let b = nodes(run("bench 3\n", "nodes"));
+        assert_eq!(a, b);
+        assert!(a.parse::<u64>().unwrap() > 0);
+    }
+}
@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod tests {
+    fn check(pos: &mut ataxx::Board, depth: u8) {
+        if depth == 0 || pos.is_gameover() {
+            return;
+        }
+
+        let moves = pos.legal_moves();
+
+        for mv in &moves {
+            let before = *pos;
+            let undo = pos.makemove(mv);
+            assert_eq!(*pos, before.after_move(mv));
+            check(pos, depth - 1);
+            pos.unmakemove(mv, &undo);
+            assert_eq!(*pos, before);
+        }
+    }
+
+    #[test]
+    fn unmake() {
+        let tests: [&str; 6] = [
+            "x5o/7/7/7/7/7/o5x x 0 1",
+            "x5o/7/7/7/7/7/o5x o 0 1",
+            "x5o/7/2-1-2/7/2-1-2/7/o5x x 20 40",
+            "7/7/7/2x1o2/7/7/7 o 0 1",
+            "7/7/7/7/ooooooo/ooooooo/xxxxxxx x 0 1",
+            "7/7/7/7/-------/-------/x5o x 0 1",
+        ];
+
+        for fen in tests {
+            let mut pos = ataxx::Board::from_fen(fen);
+            check(&mut pos, 3);
+        }
+    }
+}